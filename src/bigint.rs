@@ -0,0 +1,378 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Add, BitAnd, BitOr, BitXor, Mul, Neg, Not, Shl, Shr, Sub};
+
+/// Arbitrary-precision integer used to evaluate comptime numbers
+#[derive(Clone, Eq, PartialEq)]
+pub struct BigInt {
+    negative: bool,
+    // Little-endian limbs, without trailing zeros
+    limbs: Vec<u32>,
+}
+
+impl BigInt {
+
+    pub fn zero() -> Self {
+        return Self {
+            negative: false,
+            limbs: vec![],
+        }
+    }
+
+    fn new(negative: bool, mut limbs: Vec<u32>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        let negative = negative && !limbs.is_empty();
+        return Self {
+            negative,
+            limbs,
+        }
+    }
+
+    pub fn parse(str: &str) -> Option<Self> {
+        let (negative, digits) = if let Some(digits) = str.strip_prefix('-') {
+            (true, digits)
+        } else {
+            (false, str)
+        };
+        if digits.is_empty() {
+            return None;
+        }
+
+        let mut limbs = vec![];
+        for chr in digits.chars() {
+            let digit = chr.to_digit(10)?;
+            let mut carry = digit as u64;
+            for limb in limbs.iter_mut() {
+                let value = *limb as u64 * 10 + carry;
+                *limb = value as u32;
+                carry = value >> 32;
+            }
+            if carry != 0 {
+                limbs.push(carry as u32);
+            }
+        }
+        return Some(Self::new(negative, limbs));
+    }
+
+    pub fn from_i128(value: i128) -> Self {
+        let mut magnitude = value.unsigned_abs();
+        let mut limbs = vec![];
+        while magnitude != 0 {
+            limbs.push(magnitude as u32);
+            magnitude >>= 32;
+        }
+        return Self::new(value < 0, limbs);
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        if self.limbs.len() > 4 {
+            return None;
+        }
+        let mut magnitude: u128 = 0;
+        for limb in self.limbs.iter().rev() {
+            magnitude = (magnitude << 32) | *limb as u128;
+        }
+        return if self.negative {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        return self.limbs.is_empty();
+    }
+
+    fn compare_magnitude(left: &[u32], right: &[u32]) -> Ordering {
+        if left.len() != right.len() {
+            return left.len().cmp(&right.len());
+        }
+        for (left, right) in left.iter().rev().zip(right.iter().rev()) {
+            if left != right {
+                return left.cmp(right);
+            }
+        }
+        return Ordering::Equal;
+    }
+
+    fn add_magnitude(left: &[u32], right: &[u32]) -> Vec<u32> {
+        let mut out = Vec::with_capacity(left.len().max(right.len()) + 1);
+        let mut carry = 0u64;
+        for i in 0..left.len().max(right.len()) {
+            let value = *left.get(i).unwrap_or(&0) as u64 + *right.get(i).unwrap_or(&0) as u64 + carry;
+            out.push(value as u32);
+            carry = value >> 32;
+        }
+        out.push(carry as u32);
+        return out;
+    }
+
+    // Requires left >= right
+    fn sub_magnitude(left: &[u32], right: &[u32]) -> Vec<u32> {
+        let mut out = Vec::with_capacity(left.len());
+        let mut borrow = 0i64;
+        for i in 0..left.len() {
+            let mut value = left[i] as i64 - *right.get(i).unwrap_or(&0) as i64 - borrow;
+            borrow = if value < 0 {
+                value += 1 << 32;
+                1
+            } else {
+                0
+            };
+            out.push(value as u32);
+        }
+        return out;
+    }
+
+    fn add_signed(&self, other: &BigInt, other_negative: bool) -> BigInt {
+        if self.negative == other_negative {
+            return Self::new(self.negative, Self::add_magnitude(&self.limbs, &other.limbs));
+        }
+        return match Self::compare_magnitude(&self.limbs, &other.limbs) {
+            Ordering::Less => Self::new(other_negative, Self::sub_magnitude(&other.limbs, &self.limbs)),
+            _ => Self::new(self.negative, Self::sub_magnitude(&self.limbs, &other.limbs)),
+        }
+    }
+
+    // Truncated division of the magnitudes, returns the quotient and the remainder
+    fn div_rem_magnitude(left: &[u32], right: &[u32]) -> (Vec<u32>, Vec<u32>) {
+        let mut quotient = vec![0u32; left.len()];
+        let mut remainder = BigInt::zero();
+        let divisor = BigInt::new(false, right.to_vec());
+        for bit in (0..left.len() * 32).rev() {
+            remainder = &remainder << 1;
+            if (left[bit / 32] >> (bit % 32)) & 1 == 1 {
+                remainder = &remainder + &BigInt::from_i128(1);
+            }
+            if Self::compare_magnitude(&remainder.limbs, &divisor.limbs) != Ordering::Less {
+                remainder = &remainder - &divisor;
+                quotient[bit / 32] |= 1 << (bit % 32);
+            }
+        }
+        return (quotient, remainder.limbs);
+    }
+
+    /// Division truncating towards zero, like C
+    pub fn checked_div(&self, other: &BigInt) -> Option<BigInt> {
+        if other.is_zero() {
+            return None;
+        }
+        let (quotient, _) = Self::div_rem_magnitude(&self.limbs, &other.limbs);
+        return Some(Self::new(self.negative != other.negative, quotient));
+    }
+
+    /// Remainder with the sign of the dividend, like C
+    pub fn checked_rem(&self, other: &BigInt) -> Option<BigInt> {
+        if other.is_zero() {
+            return None;
+        }
+        let (_, remainder) = Self::div_rem_magnitude(&self.limbs, &other.limbs);
+        return Some(Self::new(self.negative, remainder));
+    }
+
+    // Two's complement representation on `len` limbs
+    fn to_twos_complement(&self, len: usize) -> Vec<u32> {
+        let mut limbs = self.limbs.clone();
+        limbs.resize(len, 0);
+        if self.negative {
+            let mut carry = 1u64;
+            for limb in limbs.iter_mut() {
+                let value = (!*limb) as u64 + carry;
+                *limb = value as u32;
+                carry = value >> 32;
+            }
+        }
+        return limbs;
+    }
+
+    fn from_twos_complement(mut limbs: Vec<u32>) -> BigInt {
+        let negative = limbs.last().map(|limb| limb >> 31 == 1).unwrap_or(false);
+        if negative {
+            let mut carry = 1u64;
+            for limb in limbs.iter_mut() {
+                let value = (!*limb) as u64 + carry;
+                *limb = value as u32;
+                carry = value >> 32;
+            }
+        }
+        return Self::new(negative, limbs);
+    }
+
+    fn bitwise(&self, other: &BigInt, op: fn(u32, u32) -> u32) -> BigInt {
+        let len = self.limbs.len().max(other.limbs.len()) + 1;
+        let left = self.to_twos_complement(len);
+        let right = other.to_twos_complement(len);
+        return Self::from_twos_complement(left.iter().zip(right.iter()).map(|(left, right)| op(*left, *right)).collect());
+    }
+
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        return self.add_signed(other, other.negative);
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        return self.add_signed(other, !other.negative);
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        let mut out = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, left) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, right) in other.limbs.iter().enumerate() {
+                let value = *left as u64 * *right as u64 + out[i + j] as u64 + carry;
+                out[i + j] = value as u32;
+                carry = value >> 32;
+            }
+            out[i + other.limbs.len()] = carry as u32;
+        }
+        return BigInt::new(self.negative != other.negative, out);
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        return BigInt::new(!self.negative, self.limbs.clone());
+    }
+}
+
+impl Not for &BigInt {
+    type Output = BigInt;
+
+    fn not(self) -> BigInt {
+        // !x == -x - 1 in two's complement
+        return &(-self) - &BigInt::from_i128(1);
+    }
+}
+
+impl BitAnd for &BigInt {
+    type Output = BigInt;
+
+    fn bitand(self, other: &BigInt) -> BigInt {
+        return self.bitwise(other, |left, right| left & right);
+    }
+}
+
+impl BitOr for &BigInt {
+    type Output = BigInt;
+
+    fn bitor(self, other: &BigInt) -> BigInt {
+        return self.bitwise(other, |left, right| left | right);
+    }
+}
+
+impl BitXor for &BigInt {
+    type Output = BigInt;
+
+    fn bitxor(self, other: &BigInt) -> BigInt {
+        return self.bitwise(other, |left, right| left ^ right);
+    }
+}
+
+impl Shl<usize> for &BigInt {
+    type Output = BigInt;
+
+    fn shl(self, amount: usize) -> BigInt {
+        let (limbs, bits) = (amount / 32, amount % 32);
+        let mut out = vec![0u32; limbs];
+        let mut carry = 0u32;
+        for limb in self.limbs.iter() {
+            out.push((limb << bits) | carry);
+            carry = if bits == 0 { 0 } else { limb >> (32 - bits) };
+        }
+        out.push(carry);
+        return BigInt::new(self.negative, out);
+    }
+}
+
+impl Shr<usize> for &BigInt {
+    type Output = BigInt;
+
+    /// Arithmetic shift, rounding towards negative infinity
+    fn shr(self, amount: usize) -> BigInt {
+        if self.negative {
+            let one = BigInt::from_i128(1);
+            let magnitude = &(-self) - &one;
+            return &(-&(&magnitude >> amount)) - &one;
+        }
+        let (limbs, bits) = (amount / 32, amount % 32);
+        if limbs >= self.limbs.len() {
+            return BigInt::zero();
+        }
+        let mut out = vec![];
+        for i in limbs..self.limbs.len() {
+            let high = if bits == 0 { 0 } else { self.limbs.get(i + 1).map(|limb| limb << (32 - bits)).unwrap_or(0) };
+            out.push((self.limbs[i] >> bits) | high);
+        }
+        return BigInt::new(false, out);
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        return match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => Self::compare_magnitude(&self.limbs, &other.limbs),
+            (true, true) => Self::compare_magnitude(&other.limbs, &self.limbs),
+        }
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        // Split into base 10^9 chunks
+        let mut chunks = vec![];
+        let mut limbs = self.limbs.clone();
+        while !limbs.is_empty() {
+            let mut remainder = 0u64;
+            for limb in limbs.iter_mut().rev() {
+                let value = (remainder << 32) | *limb as u64;
+                *limb = (value / 1_000_000_000) as u32;
+                remainder = value % 1_000_000_000;
+            }
+            while limbs.last() == Some(&0) {
+                limbs.pop();
+            }
+            chunks.push(remainder);
+        }
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", chunks.last().unwrap())?;
+        for chunk in chunks.iter().rev().skip(1) {
+            write!(f, "{:09}", chunk)?;
+        }
+        return Ok(());
+    }
+}
+
+impl Debug for BigInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{}", self);
+    }
+}
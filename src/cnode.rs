@@ -7,11 +7,30 @@ pub enum CNode {
     Value(CValueNode),
     VariableDef(Positioned<CType>, bool, Positioned<String>, Option<Box<Positioned<CNode>>>),
     VariableCall(String),
-    VariableAssignment(bool, Positioned<String>, Box<Positioned<CNode>>),
     Casting(Box<Positioned<CNode>>, Positioned<CType>),
     FunctionDefinition(Positioned<CType>, Positioned<String>, Vec<(Positioned<CType>, Positioned<String>)>, Vec<Positioned<CNode>>),
     FunctionCall(Positioned<String>, Vec<Positioned<CNode>>),
     Return(Box<Positioned<CNode>>),
+    If(Vec<(Positioned<CNode>, Vec<Positioned<CNode>>)>, Option<Vec<Positioned<CNode>>>),
+    While(Box<Positioned<CNode>>, Vec<Positioned<CNode>>),
+    For(Positioned<CType>, Positioned<String>, Box<Positioned<CNode>>, Box<Positioned<CNode>>, Option<Positioned<String>>, Vec<Positioned<CNode>>),
+    StructDeclaration(Positioned<String>),
+    EnumDefinition(Positioned<String>, Positioned<CType>, Vec<(Positioned<String>, String)>),
+    StructDefinition(Positioned<String>, Vec<(Positioned<CType>, Positioned<String>)>),
+    StructLiteral(Positioned<String>, Vec<(Positioned<String>, Positioned<CNode>)>),
+    UnionDefinition(Positioned<String>, Vec<(Positioned<String>, Vec<Positioned<CType>>)>),
+    UnionLiteral(Positioned<String>, Positioned<String>, Vec<Positioned<CNode>>),
+    Typedef(Positioned<CType>, Positioned<String>),
+    Switch(Box<Positioned<CNode>>, Vec<(Vec<Positioned<CNode>>, Vec<Positioned<CNode>>)>, Option<Vec<Positioned<CNode>>>),
+    Block(Vec<Positioned<CNode>>),
+    Label(String),
+    Goto(String),
+    FieldAccess(Box<Positioned<CNode>>, Positioned<String>),
+    ArrayLiteral(Positioned<CType>, Vec<Positioned<CNode>>),
+    Index(Box<Positioned<CNode>>, Box<Positioned<CNode>>),
+    Assignment(Box<Positioned<CNode>>, Option<Positioned<COperator>>, Box<Positioned<CNode>>),
+    Break,
+    Continue,
     Include(Positioned<String>),
 }
 
@@ -50,16 +69,28 @@ pub enum CValueNode {
 
 #[derive(Clone, Debug)]
 pub enum CType {
-    Byte,
-    UnsignedByte,
-    Short,
-    UnsignedShort,
+    Int8,
+    UnsignedInt8,
+    Int16,
+    UnsignedInt16,
+    Int32,
+    UnsignedInt32,
+    Int64,
+    UnsignedInt64,
     Int,
-    UnsignedInt,
     Long,
-    UnsignedLong,
+    Bool,
+    Float,
+    Double,
     Char,
     Ref(Box<Positioned<CType>>),
     ConstRef(Box<Positioned<CType>>),
     Void,
+    Struct(String),
+    Enum(String),
+    Array(Box<Positioned<CType>>, usize),
+    Size,
+    PtrDiff,
+    Function(Box<Positioned<CType>>, Vec<Positioned<CType>>),
+    Typedef(String),
 }
\ No newline at end of file
@@ -92,15 +92,23 @@ impl Generator {
     }
 
     fn generate_type(&mut self, data_type: Positioned<CType>) -> String {
+        if Self::needs_declarator(&data_type.data) {
+            return self.generate_declarator(data_type, String::new());
+        }
         return match data_type.data {
-            CType::Byte => "byte".to_string(),
-            CType::UnsignedByte => "unsigned byte".to_string(),
-            CType::Short => "short".to_string(),
-            CType::UnsignedShort => "unsigned short".to_string(),
+            CType::Int8 => "int8_t".to_string(),
+            CType::UnsignedInt8 => "uint8_t".to_string(),
+            CType::Int16 => "int16_t".to_string(),
+            CType::UnsignedInt16 => "uint16_t".to_string(),
+            CType::Int32 => "int32_t".to_string(),
+            CType::UnsignedInt32 => "uint32_t".to_string(),
+            CType::Int64 => "int64_t".to_string(),
+            CType::UnsignedInt64 => "uint64_t".to_string(),
             CType::Int => "int".to_string(),
-            CType::UnsignedInt => "unsigned int".to_string(),
             CType::Long => "long".to_string(),
-            CType::UnsignedLong => "unsigned long".to_string(),
+            CType::Bool => "bool".to_string(),
+            CType::Float => "float".to_string(),
+            CType::Double => "double".to_string(),
             CType::Char => "char".to_string(),
             CType::Ref(inner) => {
                 let mut str = String::new();
@@ -116,21 +124,98 @@ impl Generator {
                 str
             }
             CType::Void => "void".to_string(),
+            CType::Struct(name) => name,
+            CType::Enum(name) => name,
+            CType::Typedef(name) => name,
+            CType::Array(_, _) |
+            CType::Function(_, _) => panic!("Should not happen!"),
+            CType::Size => "size_t".to_string(),
+            CType::PtrDiff => "ptrdiff_t".to_string(),
+        }
+    }
+
+    fn needs_declarator(data_type: &CType) -> bool {
+        return match data_type {
+            CType::Array(_, _) | CType::Function(_, _) => true,
+            CType::Ref(inner) | CType::ConstRef(inner) => Self::needs_declarator(&inner.data),
+            _ => false,
+        }
+    }
+
+    fn pointer_declarator(inner: &CType, declarator: String) -> String {
+        return match inner {
+            CType::Array(_, _) | CType::Function(_, _) => format!("(*{})", declarator),
+            _ => format!("*{}", declarator),
+        }
+    }
+
+    fn generate_declarator(&mut self, data_type: Positioned<CType>, declarator: String) -> String {
+        // Arrays and functions are declared around the name, `int (*name)[3]`, `int (*name)(int)`
+        if Self::needs_declarator(&data_type.data) {
+            match data_type.data {
+                CType::Array(inner, size) => return self.generate_declarator(*inner, format!("{}[{}]", declarator, size)),
+                CType::Function(return_type, params) => {
+                    let mut c_params = vec![];
+                    for param in params {
+                        c_params.push(self.generate_type(param));
+                    }
+                    let c_params = if c_params.is_empty() { "void".to_string() } else { c_params.join(", ") };
+                    return self.generate_declarator(*return_type, format!("{}({})", declarator, c_params));
+                }
+                CType::Ref(inner) => {
+                    let declarator = Self::pointer_declarator(&inner.data, declarator);
+                    return self.generate_declarator(*inner, declarator);
+                }
+                CType::ConstRef(inner) => {
+                    // The function pointer itself is what is constant
+                    if let CType::Ref(function) = &inner.data {
+                        if let CType::Function(_, _) = function.data {
+                            return self.generate_declarator(*inner, format!("const *{}", declarator));
+                        }
+                    }
+                    let declarator = Self::pointer_declarator(&inner.data, declarator);
+                    return format!("const {}", self.generate_declarator(*inner, declarator));
+                }
+                _ => panic!("Should not happen!"),
+            }
+        }
+
+        let mut str = self.generate_type(data_type);
+        if !declarator.is_empty() {
+            str.push(' ');
+            str.push_str(declarator.as_str());
+        }
+        return str;
+    }
+
+    fn generate_initializer(&mut self, value: Positioned<CNode>) -> String {
+        // Arrays can only be initialized with a brace list
+        return if let CNode::ArrayLiteral(_, elements) = value.data {
+            let mut str = String::new();
+            str.push_str("{ ");
+            let mut first = true;
+            for element in elements {
+                if !first {
+                    str.push_str(", ");
+                }
+                str.push_str(self.generate_initializer(element).as_str());
+                first = false;
+            }
+            str.push_str(" }");
+            str
+        } else {
+            self.generate_node(value).1
         }
     }
 
     fn generate_variable_def(&mut self, data_type: Positioned<CType>, is_const: bool, name: Positioned<String>, value: Option<Box<Positioned<CNode>>>) -> String {
         let mut str = String::new();
 
-        str.push_str(self.generate_type(data_type).as_str());
-        if is_const {
-            str.push_str(" const");
-        }
-        str.push(' ');
-        str.push_str(name.data.as_str());
+        let declarator = if is_const { format!("const {}", name.data) } else { name.data.clone() };
+        str.push_str(self.generate_declarator(data_type, declarator).as_str());
         if let Some(value) = value {
             str.push_str(" = ");
-            str.push_str(self.generate_node(*value).1.as_str());
+            str.push_str(self.generate_initializer(*value).as_str());
         }
 
         return str;
@@ -149,45 +234,73 @@ impl Generator {
         return id.data.clone();
     }
 
-    fn generate_variable_assignment(&mut self, deref: bool, id: Positioned<String>, value: Positioned<CNode>) -> String {
-        let mut str = String::new();
-        if deref {
-            str.push_str("*");
+    fn generate_assignment_operator(&mut self, operator: Option<Positioned<COperator>>) -> String {
+        return if let Some(operator) = operator {
+            format!(" {}= ", self.generate_operator(operator))
+        } else {
+            " = ".to_string()
         }
-        str.push_str(id.data.as_str());
-        str.push_str(" = ");
-        str.push_str(self.generate_node(value).1.as_str());
-        return str;
     }
 
     fn generate_function_definition(&mut self, return_type: Positioned<CType>, name: Positioned<String>, params: Vec<(Positioned<CType>, Positioned<String>)>, body: Vec<Positioned<CNode>>) -> String {
-        let mut str = String::new();
-        str.push_str(self.generate_type(return_type).as_str());
-        str.push_str(" ");
-        str.push_str(name.data.as_str());
-        str.push_str("(");
+        let mut signature = String::new();
+        signature.push_str(name.data.as_str());
+        signature.push_str("(");
         let mut first = true;
         for (param_type, param_name) in params.iter() {
             if !first {
-                str.push_str(", ");
+                signature.push_str(", ");
             }
-            str.push_str(self.generate_type(param_type.clone()).as_str());
-            str.push_str(" ");
-            str.push_str(param_name.data.as_str());
+            signature.push_str(self.generate_declarator(param_type.clone(), param_name.data.clone()).as_str());
             first = false;
         }
-        str.push_str(") {\n");
+        signature.push_str(")");
+
+        let mut str = String::new();
+        str.push_str(self.generate_declarator(return_type, signature).as_str());
+        str.push_str(" ");
+        str.push_str(self.generate_body(body).as_str());
+        str
+    }
+
+    fn generate_body(&mut self, body: Vec<Positioned<CNode>>) -> String {
+        let mut str = String::new();
+        str.push_str("{\n");
         for node in body {
-            for line in self.generate_node(node).1.lines() {
+            let mut res = self.generate_node(node);
+            if res.0 {
+                res.1.push(';');
+            }
+            for line in res.1.lines() {
                 str.push_str("\t");
                 str.push_str(line);
-                str.push_str(";\n");
+                str.push_str("\n");
             }
         }
         str.push_str("}");
         str
     }
 
+    fn generate_if(&mut self, branches: Vec<(Positioned<CNode>, Vec<Positioned<CNode>>)>, else_body: Option<Vec<Positioned<CNode>>>) -> String {
+        let mut str = String::new();
+        let mut first = true;
+        for (condition, body) in branches {
+            if !first {
+                str.push_str(" else ");
+            }
+            str.push_str("if (");
+            str.push_str(self.generate_node(condition).1.as_str());
+            str.push_str(") ");
+            str.push_str(self.generate_body(body).as_str());
+            first = false;
+        }
+        if let Some(else_body) = else_body {
+            str.push_str(" else ");
+            str.push_str(self.generate_body(else_body).as_str());
+        }
+        str
+    }
+
     fn generate_function_call(&mut self, name: Positioned<String>, params: Vec<Positioned<CNode>>) -> String {
         let mut str = String::new();
         str.push_str(name.data.as_str());
@@ -204,6 +317,227 @@ impl Generator {
         str
     }
 
+    fn generate_while(&mut self, condition: Positioned<CNode>, body: Vec<Positioned<CNode>>) -> String {
+        let mut str = String::new();
+        str.push_str("while (");
+        str.push_str(self.generate_node(condition).1.as_str());
+        str.push_str(") ");
+        str.push_str(self.generate_body(body).as_str());
+        str
+    }
+
+    fn generate_for(&mut self, data_type: Positioned<CType>, name: Positioned<String>, start: Positioned<CNode>, end: Positioned<CNode>, flag: Option<Positioned<String>>, body: Vec<Positioned<CNode>>) -> String {
+        let mut str = String::new();
+        str.push_str("for (");
+        str.push_str(self.generate_type(data_type).as_str());
+        str.push(' ');
+        str.push_str(name.data.as_str());
+        str.push_str(" = ");
+        str.push_str(self.generate_node(start).1.as_str());
+        let end = self.generate_node(end).1;
+        if let Some(flag) = flag {
+            // for (T i = start, next = i <= end; next; next = i != end, i++)
+            str.push_str(format!(", {} = {} <= {}; {}; {} = {} != {}, ", flag.data, name.data, end, flag.data, flag.data, name.data, end).as_str());
+        } else {
+            str.push_str(format!("; {} < {}; ", name.data, end).as_str());
+        }
+        str.push_str(name.data.as_str());
+        str.push_str("++) ");
+        str.push_str(self.generate_body(body).as_str());
+        str
+    }
+
+    fn generate_struct_declaration(&mut self, name: Positioned<String>) -> String {
+        return format!("typedef struct {} {}", name.data, name.data);
+    }
+
+    fn generate_typedef(&mut self, data_type: Positioned<CType>, name: Positioned<String>) -> String {
+        return format!("typedef {}", self.generate_declarator(data_type, name.data));
+    }
+
+    fn generate_struct_definition(&mut self, name: Positioned<String>, fields: Vec<(Positioned<CType>, Positioned<String>)>) -> String {
+        let mut str = String::new();
+        str.push_str("struct ");
+        str.push_str(name.data.as_str());
+        str.push_str(" {\n");
+        for (field_type, field_name) in fields {
+            str.push_str("\t");
+            str.push_str(self.generate_variable_def(field_type, false, field_name, None).as_str());
+            str.push_str(";\n");
+        }
+        str.push_str("}");
+        str
+    }
+
+    fn generate_enum_definition(&mut self, name: Positioned<String>, backing_type: Positioned<CType>, variants: Vec<(Positioned<String>, String)>) -> String {
+        let mut str = String::new();
+        str.push_str("typedef ");
+        str.push_str(self.generate_type(backing_type).as_str());
+        str.push(' ');
+        str.push_str(name.data.as_str());
+        str.push_str(";\n");
+
+        // C enum constants have to fit in an int, otherwise fallback on macros
+        let fits_int = variants.iter().all(|(_, value)| {
+            value.parse::<i128>().map_or(false, |value| value >= i32::MIN as i128 && value <= i32::MAX as i128)
+        });
+        if fits_int {
+            str.push_str("enum {\n");
+            for (variant_name, value) in variants {
+                str.push_str(format!("\t{} = {},\n", variant_name.data, value).as_str());
+            }
+            str.push_str("};");
+        } else {
+            let mut first = true;
+            for (variant_name, value) in variants {
+                if !first {
+                    str.push('\n');
+                }
+                str.push_str(format!("#define {} (({}) {})", variant_name.data, name.data, value).as_str());
+                first = false;
+            }
+        }
+        str
+    }
+
+    fn generate_struct_literal(&mut self, name: Positioned<String>, fields: Vec<(Positioned<String>, Positioned<CNode>)>) -> String {
+        let mut str = String::new();
+        str.push('(');
+        str.push_str(name.data.as_str());
+        str.push_str(") { ");
+        let mut first = true;
+        for (field_name, field_value) in fields {
+            if !first {
+                str.push_str(", ");
+            }
+            str.push('.');
+            str.push_str(field_name.data.as_str());
+            str.push_str(" = ");
+            str.push_str(self.generate_initializer(field_value).as_str());
+            first = false;
+        }
+        str.push_str(" }");
+        str
+    }
+
+    fn generate_union_definition(&mut self, name: Positioned<String>, variants: Vec<(Positioned<String>, Vec<Positioned<CType>>)>) -> String {
+        let mut str = String::new();
+
+        // Tag & payloads, variants without a payload don't need any storage.
+        // The tag enum is anonymous, so that no type name can collide with a variant
+        str.push_str("struct ");
+        str.push_str(name.data.as_str());
+        str.push_str(" {\n");
+        str.push_str("\tenum {\n");
+        for (variant_name, _) in variants.iter() {
+            str.push_str(format!("\t\t{}__{},\n", name.data, variant_name.data).as_str());
+        }
+        str.push_str("\t} tag;\n");
+        if variants.iter().any(|(_, payload)| !payload.is_empty()) {
+            str.push_str("\tunion {\n");
+            for (variant_name, payload) in variants {
+                if payload.is_empty() {
+                    continue;
+                }
+                str.push_str("\t\tstruct {\n");
+                for (index, p_type) in payload.into_iter().enumerate() {
+                    str.push_str("\t\t\t");
+                    str.push_str(self.generate_variable_def(p_type.clone(), false, p_type.convert(format!("_{}", index)), None).as_str());
+                    str.push_str(";\n");
+                }
+                str.push_str("\t\t} ");
+                str.push_str(variant_name.data.as_str());
+                str.push_str(";\n");
+            }
+            str.push_str("\t};\n");
+        }
+        str.push_str("}");
+        str
+    }
+
+    fn generate_union_literal(&mut self, name: Positioned<String>, variant: Positioned<String>, args: Vec<Positioned<CNode>>) -> String {
+        let mut str = String::new();
+        str.push('(');
+        str.push_str(name.data.as_str());
+        str.push_str(") { .tag = ");
+        str.push_str(format!("{}__{}", name.data, variant.data).as_str());
+        if !args.is_empty() {
+            str.push_str(", .");
+            str.push_str(variant.data.as_str());
+            str.push_str(" = { ");
+            let mut first = true;
+            for arg in args {
+                if !first {
+                    str.push_str(", ");
+                }
+                str.push_str(self.generate_initializer(arg).as_str());
+                first = false;
+            }
+            str.push_str(" }");
+        }
+        str.push_str(" }");
+        str
+    }
+
+    fn generate_switch(&mut self, value: Positioned<CNode>, cases: Vec<(Vec<Positioned<CNode>>, Vec<Positioned<CNode>>)>, default: Option<Vec<Positioned<CNode>>>) -> String {
+        let mut str = String::new();
+        str.push_str("switch (");
+        str.push_str(self.generate_node(value).1.as_str());
+        str.push_str(") {\n");
+        for (values, body) in cases {
+            let count = values.len();
+            for (index, case_value) in values.into_iter().enumerate() {
+                str.push_str("case ");
+                str.push_str(self.generate_node(case_value).1.as_str());
+                str.push(':');
+                str.push(if index + 1 == count { ' ' } else { '\n' });
+            }
+            str.push_str(self.generate_body(body).as_str());
+            str.push('\n');
+        }
+        if let Some(default) = default {
+            str.push_str("default: ");
+            str.push_str(self.generate_body(default).as_str());
+            str.push('\n');
+        }
+        str.push_str("}");
+        str
+    }
+
+    fn generate_field_access(&mut self, value: Positioned<CNode>, field: Positioned<String>) -> String {
+        let mut str = String::new();
+        str.push_str(self.generate_node(value).1.as_str());
+        str.push('.');
+        str.push_str(field.data.as_str());
+        str
+    }
+
+    fn generate_array_literal(&mut self, data_type: Positioned<CType>, elements: Vec<Positioned<CNode>>) -> String {
+        let mut str = String::new();
+        str.push('(');
+        str.push_str(self.generate_type(data_type.clone()).as_str());
+        str.push_str(") ");
+        str.push_str(self.generate_initializer(data_type.convert(CNode::ArrayLiteral(data_type.clone(), elements))).as_str());
+        str
+    }
+
+    fn generate_index(&mut self, value: Positioned<CNode>, index: Positioned<CNode>) -> String {
+        let mut str = String::new();
+        str.push_str(self.generate_node(value).1.as_str());
+        str.push('[');
+        str.push_str(self.generate_node(index).1.as_str());
+        str.push(']');
+        str
+    }
+
+    fn generate_assignment(&mut self, target: Positioned<CNode>, operator: Option<Positioned<COperator>>, value: Positioned<CNode>) -> String {
+        let mut str = String::new();
+        str.push_str(self.generate_node(target).1.as_str());
+        str.push_str(self.generate_assignment_operator(operator).as_str());
+        str.push_str(self.generate_node(value).1.as_str());
+        str
+    }
+
     fn generate_return(&mut self, node: Positioned<CNode>) -> String {
         let mut str = String::new();
         str.push_str("return ");
@@ -223,10 +557,29 @@ impl Generator {
             CNode::VariableDef(data_type, is_const, name, value) => (true, self.generate_variable_def(data_type, is_const, name, value)),
             CNode::Casting(left, right) => (true, self.generate_cast(*left, right)),
             CNode::VariableCall(id) => (true, self.generate_variable_call(node.convert(id))),
-            CNode::VariableAssignment(deref, id, value) => (true, self.generate_variable_assignment(deref, id, *value)),
             CNode::FunctionDefinition(return_type, name, params, body) => (false, self.generate_function_definition(return_type, name, params, body)),
             CNode::FunctionCall(name, params) => (true, self.generate_function_call(name, params)),
             CNode::Return(node) => (true, self.generate_return(*node)),
+            CNode::If(branches, else_body) => (false, self.generate_if(branches, else_body)),
+            CNode::While(condition, body) => (false, self.generate_while(*condition, body)),
+            CNode::For(data_type, name, start, end, flag, body) => (false, self.generate_for(data_type, name, *start, *end, flag, body)),
+            CNode::StructDeclaration(name) => (true, self.generate_struct_declaration(name)),
+            CNode::Typedef(data_type, name) => (true, self.generate_typedef(data_type, name)),
+            CNode::EnumDefinition(name, backing_type, variants) => (false, self.generate_enum_definition(name, backing_type, variants)),
+            CNode::StructDefinition(name, fields) => (true, self.generate_struct_definition(name, fields)),
+            CNode::StructLiteral(name, fields) => (true, self.generate_struct_literal(name, fields)),
+            CNode::UnionDefinition(name, variants) => (true, self.generate_union_definition(name, variants)),
+            CNode::UnionLiteral(name, variant, args) => (true, self.generate_union_literal(name, variant, args)),
+            CNode::Switch(value, cases, default) => (false, self.generate_switch(*value, cases, default)),
+            CNode::Block(body) => (false, self.generate_body(body)),
+            CNode::Label(label) => (true, format!("{}:", label)),
+            CNode::Goto(label) => (true, format!("goto {}", label)),
+            CNode::FieldAccess(value, field) => (true, self.generate_field_access(*value, field)),
+            CNode::ArrayLiteral(data_type, elements) => (true, self.generate_array_literal(data_type, elements)),
+            CNode::Index(value, index) => (true, self.generate_index(*value, *index)),
+            CNode::Assignment(target, operator, value) => (true, self.generate_assignment(*target, operator, *value)),
+            CNode::Break => (true, "break".to_string()),
+            CNode::Continue => (true, "continue".to_string()),
             CNode::Include(file) => (false, self.generate_include(file)),
         }
     }
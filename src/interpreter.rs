@@ -0,0 +1,468 @@
+use crate::bigint::BigInt;
+use crate::node::{DataType, Node, Operator, ValueNode};
+use crate::optimizer::{Optimizer, OptimizerError};
+use crate::Positioned;
+
+// Bounds of a single evaluation, runaway code is reported instead of hanging the compiler
+const MAX_STEPS: usize = 1_000_000;
+const MAX_DEPTH: usize = 256;
+// Every call nests dozens of evaluation frames on the Rust stack, more than the main thread can hold in
+// unoptimized builds. Evaluations run on their own thread, with a stack sized for MAX_DEPTH calls
+const STACK_SIZE: usize = 256 * 1024 * 1024;
+
+#[derive(Clone, Debug)]
+pub struct ComptimeFunction {
+    pub params: Vec<(Positioned<String>, Positioned<DataType>)>,
+    pub return_type: Positioned<DataType>,
+    pub body: Vec<Positioned<Node>>,
+}
+
+#[derive(Clone, Debug)]
+pub enum Value {
+    Scalar(ValueNode, DataType),
+    Array(Vec<Value>, DataType),
+    Void,
+}
+
+impl Value {
+
+    pub fn into_node(self, position: &Positioned<()>) -> Positioned<Node> {
+        return match self {
+            // Strings keep the wrapping done by the optimizer
+            Value::Scalar(value, DataType::String) => {
+                let str = position.convert(Node::Value(value));
+                position.convert(Node::Casting(Box::new(str), position.convert(DataType::String)))
+            }
+            Value::Scalar(value, _) => position.convert(Node::Value(value)),
+            Value::Array(elements, data_type) => {
+                let elements = elements.into_iter().map(|element| element.into_node(position)).collect();
+                position.convert(Node::ArrayLiteral(Some(position.convert(data_type)), elements))
+            }
+            Value::Void => position.convert(Node::Block(vec![])),
+        }
+    }
+
+}
+
+enum Flow {
+    Normal,
+    Break,
+    Continue,
+    Return(Value),
+}
+
+pub struct Interpreter<'a> {
+    functions: &'a Vec<(String, ComptimeFunction)>,
+    steps: usize,
+    // One frame per call, holding one list of variables per block
+    frames: Vec<Vec<Vec<(String, Value, DataType)>>>,
+}
+
+impl<'a> Interpreter<'a> {
+
+    pub fn new(functions: &'a Vec<(String, ComptimeFunction)>) -> Self {
+        return Self {
+            functions,
+            steps: 0,
+            frames: vec![vec![]],
+        }
+    }
+
+    pub fn run<T: Send>(functions: &Vec<(String, ComptimeFunction)>, evaluation: impl FnOnce(&mut Interpreter) -> T + Send) -> T {
+        return std::thread::scope(|scope| {
+            let thread = std::thread::Builder::new()
+                .stack_size(STACK_SIZE)
+                .spawn_scoped(scope, || evaluation(&mut Interpreter::new(functions)))
+                .expect("Failed to start the comptime interpreter");
+            thread.join().unwrap()
+        });
+    }
+
+    pub fn evaluate_block(&mut self, body: &Vec<Positioned<Node>>) -> Result<Value, Positioned<OptimizerError>> {
+        return match self.execute_body(body)? {
+            Flow::Return(value) => Ok(value),
+            _ => Ok(Value::Void),
+        }
+    }
+
+    pub fn evaluate(&mut self, node: &Positioned<Node>) -> Result<Value, Positioned<OptimizerError>> {
+        self.step(node)?;
+        return match &node.data {
+            Node::Value(value) => {
+                let data_type = match value {
+                    ValueNode::Number(_) => DataType::ComptimeNumber,
+                    ValueNode::String(_) => DataType::ComptimeString,
+                    ValueNode::Char(_) => DataType::ComptimeChar,
+                    ValueNode::Boolean(_) => DataType::ComptimeBool,
+                    ValueNode::Float(_) => return Err(Self::unsupported(node, "floats")),
+                };
+                Ok(Value::Scalar(value.clone(), data_type))
+            }
+            Node::VariableCall(name) => {
+                match self.variable(name) {
+                    Some((_, Value::Void, _)) | None => Err(Self::unsupported(node, format!("variable '{}'", name).as_str())),
+                    Some((_, value, _)) => Ok(value.clone()),
+                }
+            }
+            Node::BinaryOperation(left, operator, right) => {
+                let left_value = self.evaluate(left)?;
+                // Logical operators short-circuit
+                match (&operator.data, &left_value) {
+                    (Operator::And, Value::Scalar(ValueNode::Boolean(false), _)) |
+                    (Operator::Or, Value::Scalar(ValueNode::Boolean(true), _)) => return Ok(left_value),
+                    _ => {}
+                }
+                let right_value = self.evaluate(right)?;
+                self.binary_operation(node, left_value, operator, right_value)
+            }
+            Node::UnaryOperation(operator, value) => {
+                match self.evaluate(value)? {
+                    Value::Scalar(value, data_type) => {
+                        match Optimizer::fold_unary_op(&operator.data, &value).map_err(|err| node.convert(err))? {
+                            Some(value) => Self::fit(node, Value::Scalar(value, data_type.clone()), &data_type),
+                            None => Err(Self::unsupported(node, format!("operator '{:?}'", operator.data).as_str())),
+                        }
+                    }
+                    _ => Err(Self::unsupported(node, format!("operator '{:?}'", operator.data).as_str())),
+                }
+            }
+            Node::Casting(value, data_type) => {
+                let value = self.evaluate(value)?;
+                Self::cast(node, value, &data_type.data)
+            }
+            Node::FunctionCall(name, params) => {
+                let mut args = vec![];
+                for param in params.iter() {
+                    args.push(self.evaluate(param)?);
+                }
+                self.call(node, name, args)
+            }
+            Node::ArrayLiteral(Some(data_type), elements) => {
+                let mut values = vec![];
+                for element in elements.iter() {
+                    let value = self.evaluate(element)?;
+                    values.push(Self::fit(element, value, &data_type.data)?);
+                }
+                Ok(Value::Array(values, data_type.data.clone()))
+            }
+            Node::Index(value, index) => {
+                let array = self.evaluate(value)?;
+                let index = self.index(index)?;
+                match array {
+                    Value::Array(elements, _) => {
+                        match elements.get(index as usize) {
+                            Some(element) if index >= 0 => Ok(element.clone()),
+                            _ => Err(node.convert(OptimizerError::IndexOutOfBounds(index, elements.len()))),
+                        }
+                    }
+                    _ => Err(Self::unsupported(node, "indexing")),
+                }
+            }
+            _ => Err(Self::unsupported(node, "this expression")),
+        }
+    }
+
+    fn step(&mut self, node: &Positioned<Node>) -> Result<(), Positioned<OptimizerError>> {
+        self.steps += 1;
+        return if self.steps > MAX_STEPS {
+            Err(node.convert(OptimizerError::ComptimeStepLimit(MAX_STEPS)))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn unsupported(node: &Positioned<Node>, what: &str) -> Positioned<OptimizerError> {
+        return node.convert(OptimizerError::ComptimeUnsupported(what.to_string()));
+    }
+
+    fn variable(&mut self, name: &String) -> Option<&mut (String, Value, DataType)> {
+        return self.frames.last_mut().unwrap().iter_mut().rev()
+            .find_map(|variables| variables.iter_mut().rev().find(|(v_name, _, _)| v_name == name));
+    }
+
+    fn declare(&mut self, name: String, value: Value, data_type: DataType) {
+        self.frames.last_mut().unwrap().last_mut().unwrap().push((name, value, data_type));
+    }
+
+    fn execute_body(&mut self, body: &Vec<Positioned<Node>>) -> Result<Flow, Positioned<OptimizerError>> {
+        self.frames.last_mut().unwrap().push(vec![]);
+        for node in body.iter() {
+            let flow = self.execute(node)?;
+            if let Flow::Normal = flow {
+                continue;
+            }
+            self.frames.last_mut().unwrap().pop();
+            return Ok(flow);
+        }
+        self.frames.last_mut().unwrap().pop();
+        return Ok(Flow::Normal);
+    }
+
+    fn execute(&mut self, node: &Positioned<Node>) -> Result<Flow, Positioned<OptimizerError>> {
+        self.step(node)?;
+        match &node.data {
+            Node::VariableDefinition(_, name, data_type, value) => {
+                let data_type = data_type.as_ref().unwrap().data.unaliased();
+                let value = if let Some(value) = value {
+                    let result = self.evaluate(value)?;
+                    Self::fit(value, result, &data_type)?
+                } else {
+                    Value::Void
+                };
+                self.declare(name.data.clone(), value, data_type);
+            }
+            Node::Assignment(target, operator, value) => {
+                let mut value = self.evaluate(value)?;
+                if let Some(operator) = operator {
+                    let current = self.evaluate(target)?;
+                    value = self.binary_operation(node, current, operator, value)?;
+                }
+                self.assign(target, value)?;
+            }
+            Node::Return(value) => return Ok(Flow::Return(self.evaluate(value)?)),
+            Node::If(branches, else_body) => {
+                for (condition, body) in branches.iter() {
+                    if self.condition(condition)? {
+                        return self.execute_body(body);
+                    }
+                }
+                if let Some(else_body) = else_body {
+                    return self.execute_body(else_body);
+                }
+            }
+            Node::While(condition, body) => {
+                while self.condition(condition)? {
+                    match self.execute_body(body)? {
+                        Flow::Break => break,
+                        Flow::Return(value) => return Ok(Flow::Return(value)),
+                        _ => {}
+                    }
+                }
+            }
+            Node::For(name, data_type, start, end, inclusive, body) => {
+                let data_type = data_type.as_ref().unwrap().data.clone();
+                let mut current = self.integer(start)?;
+                let end = self.integer(end)?;
+                let one = BigInt::from_i128(1);
+                while current < end || (*inclusive && current == end) {
+                    self.step(node)?;
+                    self.frames.last_mut().unwrap().push(vec![]);
+                    self.declare(name.data.clone(), Value::Scalar(ValueNode::Number(current.to_string()), data_type.clone()), data_type.clone());
+                    let flow = self.execute_body(body)?;
+                    self.frames.last_mut().unwrap().pop();
+                    match flow {
+                        Flow::Break => break,
+                        Flow::Return(value) => return Ok(Flow::Return(value)),
+                        _ => {}
+                    }
+                    current = &current + &one;
+                }
+            }
+            Node::Switch(value, _, cases, else_body) => {
+                let subject = self.case_number(value)?;
+                for (patterns, body) in cases.iter() {
+                    for (pattern, range_end) in patterns.iter() {
+                        let start = self.case_number(pattern)?;
+                        let matched = match range_end {
+                            Some((range_end, _)) => start <= subject && subject <= self.case_number(range_end)?,
+                            None => start == subject,
+                        };
+                        if matched {
+                            return self.execute_body(body);
+                        }
+                    }
+                }
+                if let Some(else_body) = else_body {
+                    return self.execute_body(else_body);
+                }
+            }
+            Node::Block(body) => return self.execute_body(body),
+            Node::Break => return Ok(Flow::Break),
+            Node::Continue => return Ok(Flow::Continue),
+            Node::Defer(_) => return Err(Self::unsupported(node, "defer statements")),
+            _ => {
+                self.evaluate(node)?;
+            }
+        }
+        return Ok(Flow::Normal);
+    }
+
+    fn condition(&mut self, node: &Positioned<Node>) -> Result<bool, Positioned<OptimizerError>> {
+        return match self.evaluate(node)? {
+            Value::Scalar(ValueNode::Boolean(bool), _) => Ok(bool),
+            _ => Err(Self::unsupported(node, "this condition")),
+        }
+    }
+
+    fn integer(&mut self, node: &Positioned<Node>) -> Result<BigInt, Positioned<OptimizerError>> {
+        return match self.evaluate(node)? {
+            Value::Scalar(ValueNode::Number(num), _) => Ok(BigInt::parse(&num).unwrap()),
+            _ => Err(Self::unsupported(node, "this integer")),
+        }
+    }
+
+    // Switch cases compare chars by their code
+    fn case_number(&mut self, node: &Positioned<Node>) -> Result<BigInt, Positioned<OptimizerError>> {
+        return match self.evaluate(node)? {
+            Value::Scalar(ValueNode::Number(num), _) => Ok(BigInt::parse(&num).unwrap()),
+            Value::Scalar(ValueNode::Char(chr), _) => match Optimizer::decode_char(&chr) {
+                Some(chr) => Ok(BigInt::from_i128(chr as i128)),
+                None => Err(Self::unsupported(node, "this char")),
+            },
+            _ => Err(Self::unsupported(node, "this switch")),
+        }
+    }
+
+    fn index(&mut self, node: &Positioned<Node>) -> Result<i128, Positioned<OptimizerError>> {
+        let index = self.integer(node)?;
+        return Ok(index.to_i128().unwrap_or(i128::MAX));
+    }
+
+    fn binary_operation(&mut self, node: &Positioned<Node>, left: Value, operator: &Positioned<Operator>, right: Value) -> Result<Value, Positioned<OptimizerError>> {
+        return match (left, right) {
+            (Value::Scalar(left, left_type), Value::Scalar(right, right_type)) => {
+                match Optimizer::fold_bin_op(&left, &operator.data, &right).map_err(|err| node.convert(err))? {
+                    Some(value) => {
+                        // Integers take the concrete type of their operands, the shift amount aside
+                        let data_type = match (&value, operator.data.clone()) {
+                            (ValueNode::Number(_), Operator::LeftShift | Operator::RightShift) => left_type,
+                            (ValueNode::Number(_), _) if left_type.integer_range().is_some() => left_type,
+                            (ValueNode::Number(_), _) => right_type,
+                            (ValueNode::Boolean(_), _) => DataType::Bool,
+                            _ => left_type,
+                        };
+                        Self::fit(node, Value::Scalar(value, data_type.clone()), &data_type)
+                    }
+                    None => Err(Self::unsupported(node, format!("operator '{:?}'", operator.data).as_str())),
+                }
+            }
+            _ => Err(Self::unsupported(node, format!("operator '{:?}'", operator.data).as_str())),
+        }
+    }
+
+    // Wraps an integer around the range, like a C conversion
+    fn wrap(number: BigInt, min: i128, max: i128) -> BigInt {
+        let min = BigInt::from_i128(min);
+        let modulus = &(&BigInt::from_i128(max) - &min) + &BigInt::from_i128(1);
+        let mut offset = (&number - &min).checked_rem(&modulus).unwrap();
+        if offset < BigInt::zero() {
+            offset = &offset + &modulus;
+        }
+        return &offset + &min;
+    }
+
+    /// Convert a value into the type it is stored as, unsigned integers wrap around and signed
+    /// ones report their overflow
+    fn fit(node: &Positioned<Node>, value: Value, data_type: &DataType) -> Result<Value, Positioned<OptimizerError>> {
+        return match (value, data_type) {
+            (Value::Scalar(ValueNode::Number(num), from), data_type) => {
+                if let Some((min, max)) = data_type.literal_range() {
+                    let number = BigInt::parse(&num).unwrap();
+                    if min == 0 {
+                        return Ok(Value::Scalar(ValueNode::Number(Self::wrap(number, min, max).to_string()), data_type.clone()));
+                    }
+                    if number < BigInt::from_i128(min) || number > BigInt::from_i128(max) {
+                        return Err(node.convert(OptimizerError::IntegerOutOfRange(num, data_type.clone(), min, max)));
+                    }
+                    return Ok(Value::Scalar(ValueNode::Number(num), data_type.clone()));
+                }
+                Ok(Value::Scalar(ValueNode::Number(num), if data_type.is_comptime() { from } else { data_type.clone() }))
+            }
+            (Value::Scalar(value, from), data_type) => Ok(Value::Scalar(value, if data_type.is_comptime() { from } else { data_type.clone() })),
+            (Value::Array(elements, _), DataType::Array(inner, _)) => {
+                let mut values = vec![];
+                for element in elements.into_iter() {
+                    values.push(Self::fit(node, element, &inner.data)?);
+                }
+                Ok(Value::Array(values, inner.data.clone()))
+            }
+            (value, _) => Ok(value),
+        }
+    }
+
+    fn cast(node: &Positioned<Node>, value: Value, data_type: &DataType) -> Result<Value, Positioned<OptimizerError>> {
+        let number = match &value {
+            Value::Scalar(ValueNode::Number(num), _) => BigInt::parse(num).unwrap(),
+            Value::Scalar(ValueNode::Char(chr), _) => BigInt::from_i128(Optimizer::decode_char(chr).unwrap() as i128),
+            Value::Scalar(ValueNode::Boolean(bool), _) => BigInt::from_i128(*bool as i128),
+            Value::Scalar(value, _) => return Ok(Value::Scalar(value.clone(), data_type.clone())),
+            _ => return Err(Self::unsupported(node, "this cast")),
+        };
+        return if let Some((min, max)) = data_type.integer_range() {
+            Ok(Value::Scalar(ValueNode::Number(Self::wrap(number, min, max).to_string()), data_type.clone()))
+        } else {
+            match data_type {
+                DataType::Bool => Ok(Value::Scalar(ValueNode::Boolean(!number.is_zero()), DataType::Bool)),
+                DataType::Char => {
+                    let chr = Self::wrap(number, 0, 255).to_i128().unwrap() as u8;
+                    match Optimizer::encode_char(chr, '\'') {
+                        Some(chr) => Ok(Value::Scalar(ValueNode::Char(chr), DataType::Char)),
+                        None => Err(Self::unsupported(node, "this character")),
+                    }
+                }
+                _ => Err(Self::unsupported(node, "this cast")),
+            }
+        }
+    }
+
+    fn call(&mut self, node: &Positioned<Node>, name: &Positioned<String>, args: Vec<Value>) -> Result<Value, Positioned<OptimizerError>> {
+        let function = match self.functions.iter().find(|(f_name, _)| *f_name == name.data) {
+            Some((_, function)) => function,
+            None => return Err(Self::unsupported(node, format!("a call to '{}'", name.data).as_str())),
+        };
+        if self.frames.len() > MAX_DEPTH {
+            return Err(node.convert(OptimizerError::ComptimeRecursionLimit(MAX_DEPTH)));
+        }
+
+        let mut variables = vec![];
+        for ((p_name, p_type), arg) in function.params.iter().zip(args.into_iter()) {
+            variables.push((p_name.data.clone(), Self::fit(node, arg, &p_type.data)?, p_type.data.clone()));
+        }
+        self.frames.push(vec![variables]);
+        let flow = self.execute_body(&function.body);
+        self.frames.pop();
+        return match flow? {
+            Flow::Return(value) => Self::fit(node, value, &function.return_type.data),
+            _ => Ok(Value::Void),
+        }
+    }
+
+    fn assign(&mut self, target: &Positioned<Node>, value: Value) -> Result<(), Positioned<OptimizerError>> {
+        // Walk down to the assigned variable, collecting the indices on the way
+        let mut indices = vec![];
+        let mut current = target;
+        let name = loop {
+            match &current.data {
+                Node::VariableCall(name) => break name,
+                Node::Index(value, index) => {
+                    indices.push((current, self.index(index)?));
+                    current = value;
+                }
+                _ => return Err(Self::unsupported(target, "this assignment")),
+            }
+        };
+
+        let (slot, data_type) = match self.variable(name) {
+            Some((_, slot, data_type)) => (slot, data_type.clone()),
+            None => return Err(Self::unsupported(current, format!("variable '{}'", name).as_str())),
+        };
+        let mut slot = slot;
+        let mut data_type = data_type;
+        for (node, index) in indices.into_iter().rev() {
+            match slot {
+                Value::Array(elements, inner) => {
+                    let length = elements.len();
+                    data_type = inner.clone();
+                    slot = match elements.get_mut(index as usize) {
+                        Some(element) if index >= 0 => element,
+                        _ => return Err(node.convert(OptimizerError::IndexOutOfBounds(index, length))),
+                    };
+                }
+                _ => return Err(Self::unsupported(node, "indexing")),
+            }
+        }
+        *slot = Self::fit(target, value, &data_type)?;
+        return Ok(());
+    }
+
+}
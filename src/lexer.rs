@@ -200,8 +200,32 @@ impl Lexer {
                             self.advance();
                         }
                     }
-                    '+' => tokens.push(self.make_single(Token::Plus)),
-                    '-' => tokens.push(self.make_single(Token::Minus)),
+                    '+' => {
+                        let next = self.peek(1);
+                        match next {
+                            '=' => {
+                                let start = self.pos.clone();
+                                self.advance();
+                                let mut end = self.pos.clone();
+                                end.advance(next);
+                                tokens.push(Positioned::new(Token::PlusEqual, start, end));
+                            }
+                            _ => tokens.push(self.make_single(Token::Plus)),
+                        }
+                    }
+                    '-' => {
+                        let next = self.peek(1);
+                        match next {
+                            '=' => {
+                                let start = self.pos.clone();
+                                self.advance();
+                                let mut end = self.pos.clone();
+                                end.advance(next);
+                                tokens.push(Positioned::new(Token::MinusEqual, start, end));
+                            }
+                            _ => tokens.push(self.make_single(Token::Minus)),
+                        }
+                    }
                     '/' => {
                         let next = self.peek(1);
                         match next {
@@ -241,16 +265,49 @@ impl Lexer {
                                     self.advance();
                                 }
                             }
+                            '=' => {
+                                let start = self.pos.clone();
+                                self.advance();
+                                let mut end = self.pos.clone();
+                                end.advance(next);
+                                tokens.push(Positioned::new(Token::SlashEqual, start, end));
+                            }
                             _ => tokens.push(self.make_single(Token::Slash))
                         }
                     },
-                    '*' => tokens.push(self.make_single(Token::Star)),
-                    '%' => tokens.push(self.make_single(Token::Percent)),
+                    '*' => {
+                        let next = self.peek(1);
+                        match next {
+                            '=' => {
+                                let start = self.pos.clone();
+                                self.advance();
+                                let mut end = self.pos.clone();
+                                end.advance(next);
+                                tokens.push(Positioned::new(Token::StarEqual, start, end));
+                            }
+                            _ => tokens.push(self.make_single(Token::Star)),
+                        }
+                    }
+                    '%' => {
+                        let next = self.peek(1);
+                        match next {
+                            '=' => {
+                                let start = self.pos.clone();
+                                self.advance();
+                                let mut end = self.pos.clone();
+                                end.advance(next);
+                                tokens.push(Positioned::new(Token::PercentEqual, start, end));
+                            }
+                            _ => tokens.push(self.make_single(Token::Percent)),
+                        }
+                    }
                     ';' => tokens.push(self.make_single(Token::Semicolon)),
                     '(' => tokens.push(self.make_single(Token::LeftParenthesis)),
                     ')' => tokens.push(self.make_single(Token::RightParenthesis)),
                     '{' => tokens.push(self.make_single(Token::LeftCurlyBracket)),
                     '}' => tokens.push(self.make_single(Token::RightCurlyBracket)),
+                    '[' => tokens.push(self.make_single(Token::LeftSquareBracket)),
+                    ']' => tokens.push(self.make_single(Token::RightSquareBracket)),
                     ':' => tokens.push(self.make_single(Token::Colon)),
                     ',' => tokens.push(self.make_single(Token::Comma)),
                     '~' => tokens.push(self.make_single(Token::Wave)),
@@ -260,7 +317,7 @@ impl Lexer {
                         match next {
                             '.' => {
                                 let next2 = self.peek(2);
-                                match next {
+                                match next2 {
                                     '.' => {
                                         let start = self.pos.clone();
                                         self.advance();
@@ -269,10 +326,24 @@ impl Lexer {
                                         end.advance(next2);
                                         tokens.push(Positioned::new(Token::TripleDot, start, end));
                                     }
-                                    _ => todo!("dot")
+                                    '=' => {
+                                        let start = self.pos.clone();
+                                        self.advance();
+                                        self.advance();
+                                        let mut end = self.pos.clone();
+                                        end.advance(next2);
+                                        tokens.push(Positioned::new(Token::DoubleDotEqual, start, end));
+                                    }
+                                    _ => {
+                                        let start = self.pos.clone();
+                                        self.advance();
+                                        let mut end = self.pos.clone();
+                                        end.advance(next);
+                                        tokens.push(Positioned::new(Token::DoubleDot, start, end));
+                                    }
                                 }
                             }
-                            _ => todo!("dot")
+                            _ => tokens.push(self.make_single(Token::Dot)),
                         }
                     }
                     '<' => {
@@ -281,9 +352,17 @@ impl Lexer {
                             '<' => {
                                 let start = self.pos.clone();
                                 self.advance();
-                                let mut end = self.pos.clone();
-                                end.advance(next);
-                                tokens.push(Positioned::new(Token::DoubleLeftAngle, start, end));
+                                let next2 = self.peek(1);
+                                if next2 == '=' {
+                                    self.advance();
+                                    let mut end = self.pos.clone();
+                                    end.advance(next2);
+                                    tokens.push(Positioned::new(Token::DoubleLeftAngleEqual, start, end));
+                                } else {
+                                    let mut end = self.pos.clone();
+                                    end.advance(next);
+                                    tokens.push(Positioned::new(Token::DoubleLeftAngle, start, end));
+                                }
                             }
                             '=' => {
                                 let start = self.pos.clone();
@@ -301,9 +380,17 @@ impl Lexer {
                             '>' => {
                                 let start = self.pos.clone();
                                 self.advance();
-                                let mut end = self.pos.clone();
-                                end.advance(next);
-                                tokens.push(Positioned::new(Token::DoubleRightAngle, start, end));
+                                let next2 = self.peek(1);
+                                if next2 == '=' {
+                                    self.advance();
+                                    let mut end = self.pos.clone();
+                                    end.advance(next2);
+                                    tokens.push(Positioned::new(Token::DoubleRightAngleEqual, start, end));
+                                } else {
+                                    let mut end = self.pos.clone();
+                                    end.advance(next);
+                                    tokens.push(Positioned::new(Token::DoubleRightAngle, start, end));
+                                }
                             }
                             '=' => {
                                 let start = self.pos.clone();
@@ -338,6 +425,13 @@ impl Lexer {
                                 end.advance(next);
                                 tokens.push(Positioned::new(Token::DoubleEqual, start, end));
                             }
+                            '>' => {
+                                let start = self.pos.clone();
+                                self.advance();
+                                let mut end = self.pos.clone();
+                                end.advance(next);
+                                tokens.push(Positioned::new(Token::FatArrow, start, end));
+                            }
                             _ => tokens.push(self.make_single(Token::Equal)),
                         }
                     }
@@ -351,6 +445,13 @@ impl Lexer {
                                 end.advance(next);
                                 tokens.push(Positioned::new(Token::DoubleAnd, start, end));
                             }
+                            '=' => {
+                                let start = self.pos.clone();
+                                self.advance();
+                                let mut end = self.pos.clone();
+                                end.advance(next);
+                                tokens.push(Positioned::new(Token::AndEqual, start, end));
+                            }
                             _ => tokens.push(self.make_single(Token::And)),
                         }
                     }
@@ -364,6 +465,13 @@ impl Lexer {
                                 end.advance(next);
                                 tokens.push(Positioned::new(Token::DoublePipe, start, end));
                             }
+                            '=' => {
+                                let start = self.pos.clone();
+                                self.advance();
+                                let mut end = self.pos.clone();
+                                end.advance(next);
+                                tokens.push(Positioned::new(Token::PipeEqual, start, end));
+                            }
                             _ => tokens.push(self.make_single(Token::Pipe)),
                         }
                     }
@@ -377,6 +485,13 @@ impl Lexer {
                                 end.advance(next);
                                 tokens.push(Positioned::new(Token::DoubleHat, start, end));
                             }
+                            '=' => {
+                                let start = self.pos.clone();
+                                self.advance();
+                                let mut end = self.pos.clone();
+                                end.advance(next);
+                                tokens.push(Positioned::new(Token::HatEqual, start, end));
+                            }
                             _ => tokens.push(self.make_single(Token::Hat)),
                         }
                     }
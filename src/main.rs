@@ -20,8 +20,13 @@ mod optimizer;
 mod cnode;
 mod transpiler;
 mod generator;
+mod bigint;
+mod interpreter;
 
 fn main() {
+    // Flags
+    let warn_shadowing = std::env::args().any(|arg| arg == "--warn-shadowing");
+
     // File
     let mut str = std::fs::read_to_string("main.lead").expect("Failed to read file 'main.lead'");
 
@@ -53,9 +58,16 @@ fn main() {
 
                     // Optimizer
                     let mut optimizer = Optimizer::new(str, ast);
+                    optimizer.warn_shadowing(warn_shadowing);
                     let optimizer_result = optimizer.optimize(true);
+                    let warnings = optimizer.warnings().clone();
                     let str = optimizer.take();
 
+                    for warning in warnings {
+                        println!("[Optimizer Warning]: {} at {}:{}", warning.data, warning.start.line, warning.start.column);
+                        warning.show_on_text(str.clone());
+                    }
+
                     match optimizer_result {
                         Ok(ast) => {
                             for node in ast.iter() {
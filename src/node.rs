@@ -9,11 +9,35 @@ pub enum Node {
     Value(ValueNode),
     VariableDefinition(Positioned<VarType>, Positioned<String>, Option<Positioned<DataType>>, Option<Box<Positioned<Node>>>),
     VariableCall(String),
-    VariableAssignment(bool, Positioned<String>, Box<Positioned<Node>>),
     Casting(Box<Positioned<Node>>, Positioned<DataType>),
-    FunctionDefinition(Positioned<String>, Vec<(Positioned<String>, Positioned<DataType>)>, Option<Positioned<DataType>>, Vec<Positioned<Node>>),
+    FunctionDefinition(Positioned<String>, Vec<Positioned<String>>, Vec<(Positioned<String>, Positioned<DataType>)>, Option<Positioned<DataType>>, Vec<Positioned<Node>>),
     FunctionCall(Positioned<String>, Vec<Positioned<Node>>),
     Return(Box<Positioned<Node>>),
+    If(Vec<(Positioned<Node>, Vec<Positioned<Node>>)>, Option<Vec<Positioned<Node>>>),
+    While(Box<Positioned<Node>>, Vec<Positioned<Node>>),
+    For(Positioned<String>, Option<Positioned<DataType>>, Box<Positioned<Node>>, Box<Positioned<Node>>, bool, Vec<Positioned<Node>>),
+    StructDefinition(Positioned<String>, Vec<(Positioned<String>, Positioned<DataType>)>),
+    EnumDefinition(Positioned<String>, Option<Positioned<DataType>>, Vec<(Positioned<String>, Option<Positioned<Node>>)>),
+    EnumVariant(Positioned<String>, Positioned<String>),
+    StructLiteral(Positioned<String>, Vec<(Positioned<String>, Positioned<Node>)>),
+    UnionDefinition(Positioned<String>, Vec<(Positioned<String>, Vec<Positioned<DataType>>)>),
+    TypeAlias(Positioned<String>, Positioned<DataType>),
+    UnionLiteral(Positioned<String>, Positioned<String>, Vec<Positioned<Node>>),
+    Impl(Positioned<String>, Vec<Positioned<Node>>),
+    Match(Box<Positioned<Node>>, Option<String>, Vec<(Positioned<String>, Vec<(Positioned<String>, Option<Positioned<DataType>>)>, Vec<Positioned<Node>>)>),
+    Switch(Box<Positioned<Node>>, Option<Positioned<DataType>>, Vec<(Vec<(Positioned<Node>, Option<(Positioned<Node>, bool)>)>, Vec<Positioned<Node>>)>, Option<Vec<Positioned<Node>>>),
+    MemberCall(Box<Positioned<Node>>, Positioned<String>, Vec<Positioned<Node>>),
+    FieldAccess(Box<Positioned<Node>>, Positioned<String>),
+    ArrayLiteral(Option<Positioned<DataType>>, Vec<Positioned<Node>>),
+    Index(Box<Positioned<Node>>, Box<Positioned<Node>>),
+    Slice(Box<Positioned<Node>>, Option<Positioned<DataType>>, Option<Box<Positioned<Node>>>, Option<Box<Positioned<Node>>>),
+    SliceIndex(Box<Positioned<Node>>, Box<Positioned<Node>>, Option<Positioned<DataType>>),
+    Assignment(Box<Positioned<Node>>, Option<Positioned<Operator>>, Box<Positioned<Node>>),
+    Block(Vec<Positioned<Node>>),
+    Defer(Box<Positioned<Node>>),
+    Comptime(Vec<Positioned<Node>>),
+    Break,
+    Continue,
     CompilerInstruction(CompilerInstruction)
 }
 
@@ -27,6 +51,7 @@ pub enum CompilerInstruction {
 #[derive(Clone, Debug)]
 pub enum ValueNode {
     Number(String),
+    Float(String),
     String(String),
     Char(String),
     Boolean(bool)
@@ -75,6 +100,13 @@ impl Operator {
                     DataType::I16 => Some(DataType::I16),
                     DataType::I32 => Some(DataType::I32),
                     DataType::I64 => Some(DataType::I64),
+                    DataType::Usize => Some(DataType::Usize),
+                    DataType::Isize => Some(DataType::Isize),
+                    DataType::CInt => Some(DataType::CInt),
+                    DataType::CLong => Some(DataType::CLong),
+                    DataType::ComptimeFloat => Some(DataType::ComptimeFloat),
+                    DataType::F32 => Some(DataType::F32),
+                    DataType::F64 => Some(DataType::F64),
                     _ => None,
                 }
             },
@@ -89,6 +121,13 @@ impl Operator {
                     DataType::I16 => Some(DataType::I16),
                     DataType::I32 => Some(DataType::I32),
                     DataType::I64 => Some(DataType::I64),
+                    DataType::Usize => Some(DataType::Usize),
+                    DataType::Isize => Some(DataType::Isize),
+                    DataType::CInt => Some(DataType::CInt),
+                    DataType::CLong => Some(DataType::CLong),
+                    DataType::ComptimeFloat => Some(DataType::ComptimeFloat),
+                    DataType::F32 => Some(DataType::F32),
+                    DataType::F64 => Some(DataType::F64),
                     _ => None,
                 }
             },
@@ -110,6 +149,10 @@ impl Operator {
                     DataType::I16 => Some(DataType::I16),
                     DataType::I32 => Some(DataType::I32),
                     DataType::I64 => Some(DataType::I64),
+                    DataType::Usize => Some(DataType::Usize),
+                    DataType::Isize => Some(DataType::Isize),
+                    DataType::CInt => Some(DataType::CInt),
+                    DataType::CLong => Some(DataType::CLong),
                     _ => None,
                 }
             },
@@ -180,6 +223,69 @@ impl Operator {
                     (DataType::U32, DataType::I64) |
                     (DataType::I64, DataType::U32) |
                     (DataType::I64, DataType::I64) => Some(DataType::I64),
+                    (DataType::ComptimeNumber, DataType::Usize) |
+                    (DataType::Usize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Usize) |
+                    (DataType::Usize, DataType::U8) |
+                    (DataType::U16, DataType::Usize) |
+                    (DataType::Usize, DataType::U16) |
+                    (DataType::U32, DataType::Usize) |
+                    (DataType::Usize, DataType::U32) |
+                    (DataType::Usize, DataType::Usize) => Some(DataType::Usize),
+                    (DataType::ComptimeNumber, DataType::Isize) |
+                    (DataType::Isize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Isize) |
+                    (DataType::Isize, DataType::U8) |
+                    (DataType::U16, DataType::Isize) |
+                    (DataType::Isize, DataType::U16) |
+                    (DataType::U32, DataType::Isize) |
+                    (DataType::Isize, DataType::U32) |
+                    (DataType::I8, DataType::Isize) |
+                    (DataType::Isize, DataType::I8) |
+                    (DataType::I16, DataType::Isize) |
+                    (DataType::Isize, DataType::I16) |
+                    (DataType::I32, DataType::Isize) |
+                    (DataType::Isize, DataType::I32) |
+                    (DataType::Isize, DataType::Isize) => Some(DataType::Isize),
+                    (DataType::ComptimeNumber, DataType::CInt) |
+                    (DataType::CInt, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CInt) |
+                    (DataType::CInt, DataType::U8) |
+                    (DataType::U16, DataType::CInt) |
+                    (DataType::CInt, DataType::U16) |
+                    (DataType::I8, DataType::CInt) |
+                    (DataType::CInt, DataType::I8) |
+                    (DataType::I16, DataType::CInt) |
+                    (DataType::CInt, DataType::I16) |
+                    (DataType::CInt, DataType::CInt) => Some(DataType::CInt),
+                    (DataType::ComptimeNumber, DataType::CLong) |
+                    (DataType::CLong, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CLong) |
+                    (DataType::CLong, DataType::U8) |
+                    (DataType::U16, DataType::CLong) |
+                    (DataType::CLong, DataType::U16) |
+                    (DataType::I8, DataType::CLong) |
+                    (DataType::CLong, DataType::I8) |
+                    (DataType::I16, DataType::CLong) |
+                    (DataType::CLong, DataType::I16) |
+                    (DataType::I32, DataType::CLong) |
+                    (DataType::CLong, DataType::I32) |
+                    (DataType::CLong, DataType::CLong) => Some(DataType::CLong),
+                    (DataType::ComptimeFloat, DataType::ComptimeFloat) |
+                    (DataType::ComptimeFloat, DataType::ComptimeNumber) |
+                    (DataType::ComptimeNumber, DataType::ComptimeFloat) => Some(DataType::ComptimeFloat),
+                    (DataType::ComptimeNumber, DataType::F32) |
+                    (DataType::ComptimeFloat, DataType::F32) |
+                    (DataType::F32, DataType::ComptimeNumber) |
+                    (DataType::F32, DataType::ComptimeFloat) |
+                    (DataType::F32, DataType::F32) => Some(DataType::F32),
+                    (DataType::ComptimeNumber, DataType::F64) |
+                    (DataType::ComptimeFloat, DataType::F64) |
+                    (DataType::F64, DataType::ComptimeNumber) |
+                    (DataType::F64, DataType::ComptimeFloat) |
+                    (DataType::F32, DataType::F64) |
+                    (DataType::F64, DataType::F32) |
+                    (DataType::F64, DataType::F64) => Some(DataType::F64),
                     _ => None,
                 }
             }
@@ -234,6 +340,69 @@ impl Operator {
                     (DataType::U32, DataType::I64) |
                     (DataType::I64, DataType::U32) |
                     (DataType::I64, DataType::I64) => Some(DataType::I64),
+                    (DataType::ComptimeNumber, DataType::Usize) |
+                    (DataType::Usize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Usize) |
+                    (DataType::Usize, DataType::U8) |
+                    (DataType::U16, DataType::Usize) |
+                    (DataType::Usize, DataType::U16) |
+                    (DataType::U32, DataType::Usize) |
+                    (DataType::Usize, DataType::U32) |
+                    (DataType::Usize, DataType::Usize) => Some(DataType::Usize),
+                    (DataType::ComptimeNumber, DataType::Isize) |
+                    (DataType::Isize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Isize) |
+                    (DataType::Isize, DataType::U8) |
+                    (DataType::U16, DataType::Isize) |
+                    (DataType::Isize, DataType::U16) |
+                    (DataType::U32, DataType::Isize) |
+                    (DataType::Isize, DataType::U32) |
+                    (DataType::I8, DataType::Isize) |
+                    (DataType::Isize, DataType::I8) |
+                    (DataType::I16, DataType::Isize) |
+                    (DataType::Isize, DataType::I16) |
+                    (DataType::I32, DataType::Isize) |
+                    (DataType::Isize, DataType::I32) |
+                    (DataType::Isize, DataType::Isize) => Some(DataType::Isize),
+                    (DataType::ComptimeNumber, DataType::CInt) |
+                    (DataType::CInt, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CInt) |
+                    (DataType::CInt, DataType::U8) |
+                    (DataType::U16, DataType::CInt) |
+                    (DataType::CInt, DataType::U16) |
+                    (DataType::I8, DataType::CInt) |
+                    (DataType::CInt, DataType::I8) |
+                    (DataType::I16, DataType::CInt) |
+                    (DataType::CInt, DataType::I16) |
+                    (DataType::CInt, DataType::CInt) => Some(DataType::CInt),
+                    (DataType::ComptimeNumber, DataType::CLong) |
+                    (DataType::CLong, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CLong) |
+                    (DataType::CLong, DataType::U8) |
+                    (DataType::U16, DataType::CLong) |
+                    (DataType::CLong, DataType::U16) |
+                    (DataType::I8, DataType::CLong) |
+                    (DataType::CLong, DataType::I8) |
+                    (DataType::I16, DataType::CLong) |
+                    (DataType::CLong, DataType::I16) |
+                    (DataType::I32, DataType::CLong) |
+                    (DataType::CLong, DataType::I32) |
+                    (DataType::CLong, DataType::CLong) => Some(DataType::CLong),
+                    (DataType::ComptimeFloat, DataType::ComptimeFloat) |
+                    (DataType::ComptimeFloat, DataType::ComptimeNumber) |
+                    (DataType::ComptimeNumber, DataType::ComptimeFloat) => Some(DataType::ComptimeFloat),
+                    (DataType::ComptimeNumber, DataType::F32) |
+                    (DataType::ComptimeFloat, DataType::F32) |
+                    (DataType::F32, DataType::ComptimeNumber) |
+                    (DataType::F32, DataType::ComptimeFloat) |
+                    (DataType::F32, DataType::F32) => Some(DataType::F32),
+                    (DataType::ComptimeNumber, DataType::F64) |
+                    (DataType::ComptimeFloat, DataType::F64) |
+                    (DataType::F64, DataType::ComptimeNumber) |
+                    (DataType::F64, DataType::ComptimeFloat) |
+                    (DataType::F32, DataType::F64) |
+                    (DataType::F64, DataType::F32) |
+                    (DataType::F64, DataType::F64) => Some(DataType::F64),
                     _ => None,
                 }
             }
@@ -288,6 +457,54 @@ impl Operator {
                     (DataType::U32, DataType::I64) |
                     (DataType::I64, DataType::U32) |
                     (DataType::I64, DataType::I64) => Some(DataType::I64),
+                    (DataType::ComptimeNumber, DataType::Usize) |
+                    (DataType::Usize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Usize) |
+                    (DataType::Usize, DataType::U8) |
+                    (DataType::U16, DataType::Usize) |
+                    (DataType::Usize, DataType::U16) |
+                    (DataType::U32, DataType::Usize) |
+                    (DataType::Usize, DataType::U32) |
+                    (DataType::Usize, DataType::Usize) => Some(DataType::Usize),
+                    (DataType::ComptimeNumber, DataType::Isize) |
+                    (DataType::Isize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Isize) |
+                    (DataType::Isize, DataType::U8) |
+                    (DataType::U16, DataType::Isize) |
+                    (DataType::Isize, DataType::U16) |
+                    (DataType::U32, DataType::Isize) |
+                    (DataType::Isize, DataType::U32) |
+                    (DataType::I8, DataType::Isize) |
+                    (DataType::Isize, DataType::I8) |
+                    (DataType::I16, DataType::Isize) |
+                    (DataType::Isize, DataType::I16) |
+                    (DataType::I32, DataType::Isize) |
+                    (DataType::Isize, DataType::I32) |
+                    (DataType::Isize, DataType::Isize) => Some(DataType::Isize),
+                    (DataType::ComptimeNumber, DataType::CInt) |
+                    (DataType::CInt, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CInt) |
+                    (DataType::CInt, DataType::U8) |
+                    (DataType::U16, DataType::CInt) |
+                    (DataType::CInt, DataType::U16) |
+                    (DataType::I8, DataType::CInt) |
+                    (DataType::CInt, DataType::I8) |
+                    (DataType::I16, DataType::CInt) |
+                    (DataType::CInt, DataType::I16) |
+                    (DataType::CInt, DataType::CInt) => Some(DataType::CInt),
+                    (DataType::ComptimeNumber, DataType::CLong) |
+                    (DataType::CLong, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CLong) |
+                    (DataType::CLong, DataType::U8) |
+                    (DataType::U16, DataType::CLong) |
+                    (DataType::CLong, DataType::U16) |
+                    (DataType::I8, DataType::CLong) |
+                    (DataType::CLong, DataType::I8) |
+                    (DataType::I16, DataType::CLong) |
+                    (DataType::CLong, DataType::I16) |
+                    (DataType::I32, DataType::CLong) |
+                    (DataType::CLong, DataType::I32) |
+                    (DataType::CLong, DataType::CLong) => Some(DataType::CLong),
                     _ => None,
                 }
             }
@@ -349,6 +566,69 @@ impl Operator {
                     (DataType::U32, DataType::I64) |
                     (DataType::I64, DataType::U32) |
                     (DataType::I64, DataType::I64) => Some(DataType::I64),
+                    (DataType::ComptimeNumber, DataType::Usize) |
+                    (DataType::Usize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Usize) |
+                    (DataType::Usize, DataType::U8) |
+                    (DataType::U16, DataType::Usize) |
+                    (DataType::Usize, DataType::U16) |
+                    (DataType::U32, DataType::Usize) |
+                    (DataType::Usize, DataType::U32) |
+                    (DataType::Usize, DataType::Usize) => Some(DataType::Usize),
+                    (DataType::ComptimeNumber, DataType::Isize) |
+                    (DataType::Isize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Isize) |
+                    (DataType::Isize, DataType::U8) |
+                    (DataType::U16, DataType::Isize) |
+                    (DataType::Isize, DataType::U16) |
+                    (DataType::U32, DataType::Isize) |
+                    (DataType::Isize, DataType::U32) |
+                    (DataType::I8, DataType::Isize) |
+                    (DataType::Isize, DataType::I8) |
+                    (DataType::I16, DataType::Isize) |
+                    (DataType::Isize, DataType::I16) |
+                    (DataType::I32, DataType::Isize) |
+                    (DataType::Isize, DataType::I32) |
+                    (DataType::Isize, DataType::Isize) => Some(DataType::Isize),
+                    (DataType::ComptimeNumber, DataType::CInt) |
+                    (DataType::CInt, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CInt) |
+                    (DataType::CInt, DataType::U8) |
+                    (DataType::U16, DataType::CInt) |
+                    (DataType::CInt, DataType::U16) |
+                    (DataType::I8, DataType::CInt) |
+                    (DataType::CInt, DataType::I8) |
+                    (DataType::I16, DataType::CInt) |
+                    (DataType::CInt, DataType::I16) |
+                    (DataType::CInt, DataType::CInt) => Some(DataType::CInt),
+                    (DataType::ComptimeNumber, DataType::CLong) |
+                    (DataType::CLong, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CLong) |
+                    (DataType::CLong, DataType::U8) |
+                    (DataType::U16, DataType::CLong) |
+                    (DataType::CLong, DataType::U16) |
+                    (DataType::I8, DataType::CLong) |
+                    (DataType::CLong, DataType::I8) |
+                    (DataType::I16, DataType::CLong) |
+                    (DataType::CLong, DataType::I16) |
+                    (DataType::I32, DataType::CLong) |
+                    (DataType::CLong, DataType::I32) |
+                    (DataType::CLong, DataType::CLong) => Some(DataType::CLong),
+                    (DataType::ComptimeFloat, DataType::ComptimeFloat) |
+                    (DataType::ComptimeFloat, DataType::ComptimeNumber) |
+                    (DataType::ComptimeNumber, DataType::ComptimeFloat) => Some(DataType::ComptimeFloat),
+                    (DataType::ComptimeNumber, DataType::F32) |
+                    (DataType::ComptimeFloat, DataType::F32) |
+                    (DataType::F32, DataType::ComptimeNumber) |
+                    (DataType::F32, DataType::ComptimeFloat) |
+                    (DataType::F32, DataType::F32) => Some(DataType::F32),
+                    (DataType::ComptimeNumber, DataType::F64) |
+                    (DataType::ComptimeFloat, DataType::F64) |
+                    (DataType::F64, DataType::ComptimeNumber) |
+                    (DataType::F64, DataType::ComptimeFloat) |
+                    (DataType::F32, DataType::F64) |
+                    (DataType::F64, DataType::F32) |
+                    (DataType::F64, DataType::F64) => Some(DataType::F64),
                     _ => None,
                 }
             }
@@ -407,6 +687,69 @@ impl Operator {
                     (DataType::U32, DataType::I64) |
                     (DataType::I64, DataType::U32) |
                     (DataType::I64, DataType::I64) => Some(DataType::I64),
+                    (DataType::ComptimeNumber, DataType::Usize) |
+                    (DataType::Usize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Usize) |
+                    (DataType::Usize, DataType::U8) |
+                    (DataType::U16, DataType::Usize) |
+                    (DataType::Usize, DataType::U16) |
+                    (DataType::U32, DataType::Usize) |
+                    (DataType::Usize, DataType::U32) |
+                    (DataType::Usize, DataType::Usize) => Some(DataType::Usize),
+                    (DataType::ComptimeNumber, DataType::Isize) |
+                    (DataType::Isize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Isize) |
+                    (DataType::Isize, DataType::U8) |
+                    (DataType::U16, DataType::Isize) |
+                    (DataType::Isize, DataType::U16) |
+                    (DataType::U32, DataType::Isize) |
+                    (DataType::Isize, DataType::U32) |
+                    (DataType::I8, DataType::Isize) |
+                    (DataType::Isize, DataType::I8) |
+                    (DataType::I16, DataType::Isize) |
+                    (DataType::Isize, DataType::I16) |
+                    (DataType::I32, DataType::Isize) |
+                    (DataType::Isize, DataType::I32) |
+                    (DataType::Isize, DataType::Isize) => Some(DataType::Isize),
+                    (DataType::ComptimeNumber, DataType::CInt) |
+                    (DataType::CInt, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CInt) |
+                    (DataType::CInt, DataType::U8) |
+                    (DataType::U16, DataType::CInt) |
+                    (DataType::CInt, DataType::U16) |
+                    (DataType::I8, DataType::CInt) |
+                    (DataType::CInt, DataType::I8) |
+                    (DataType::I16, DataType::CInt) |
+                    (DataType::CInt, DataType::I16) |
+                    (DataType::CInt, DataType::CInt) => Some(DataType::CInt),
+                    (DataType::ComptimeNumber, DataType::CLong) |
+                    (DataType::CLong, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CLong) |
+                    (DataType::CLong, DataType::U8) |
+                    (DataType::U16, DataType::CLong) |
+                    (DataType::CLong, DataType::U16) |
+                    (DataType::I8, DataType::CLong) |
+                    (DataType::CLong, DataType::I8) |
+                    (DataType::I16, DataType::CLong) |
+                    (DataType::CLong, DataType::I16) |
+                    (DataType::I32, DataType::CLong) |
+                    (DataType::CLong, DataType::I32) |
+                    (DataType::CLong, DataType::CLong) => Some(DataType::CLong),
+                    (DataType::ComptimeFloat, DataType::ComptimeFloat) |
+                    (DataType::ComptimeFloat, DataType::ComptimeNumber) |
+                    (DataType::ComptimeNumber, DataType::ComptimeFloat) => Some(DataType::ComptimeFloat),
+                    (DataType::ComptimeNumber, DataType::F32) |
+                    (DataType::ComptimeFloat, DataType::F32) |
+                    (DataType::F32, DataType::ComptimeNumber) |
+                    (DataType::F32, DataType::ComptimeFloat) |
+                    (DataType::F32, DataType::F32) => Some(DataType::F32),
+                    (DataType::ComptimeNumber, DataType::F64) |
+                    (DataType::ComptimeFloat, DataType::F64) |
+                    (DataType::F64, DataType::ComptimeNumber) |
+                    (DataType::F64, DataType::ComptimeFloat) |
+                    (DataType::F32, DataType::F64) |
+                    (DataType::F64, DataType::F32) |
+                    (DataType::F64, DataType::F64) => Some(DataType::F64),
                     _ => None,
                 }
             }
@@ -461,6 +804,54 @@ impl Operator {
                     (DataType::U32, DataType::I64) |
                     (DataType::I64, DataType::U32) |
                     (DataType::I64, DataType::I64) => Some(DataType::I64),
+                    (DataType::ComptimeNumber, DataType::Usize) |
+                    (DataType::Usize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Usize) |
+                    (DataType::Usize, DataType::U8) |
+                    (DataType::U16, DataType::Usize) |
+                    (DataType::Usize, DataType::U16) |
+                    (DataType::U32, DataType::Usize) |
+                    (DataType::Usize, DataType::U32) |
+                    (DataType::Usize, DataType::Usize) => Some(DataType::Usize),
+                    (DataType::ComptimeNumber, DataType::Isize) |
+                    (DataType::Isize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Isize) |
+                    (DataType::Isize, DataType::U8) |
+                    (DataType::U16, DataType::Isize) |
+                    (DataType::Isize, DataType::U16) |
+                    (DataType::U32, DataType::Isize) |
+                    (DataType::Isize, DataType::U32) |
+                    (DataType::I8, DataType::Isize) |
+                    (DataType::Isize, DataType::I8) |
+                    (DataType::I16, DataType::Isize) |
+                    (DataType::Isize, DataType::I16) |
+                    (DataType::I32, DataType::Isize) |
+                    (DataType::Isize, DataType::I32) |
+                    (DataType::Isize, DataType::Isize) => Some(DataType::Isize),
+                    (DataType::ComptimeNumber, DataType::CInt) |
+                    (DataType::CInt, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CInt) |
+                    (DataType::CInt, DataType::U8) |
+                    (DataType::U16, DataType::CInt) |
+                    (DataType::CInt, DataType::U16) |
+                    (DataType::I8, DataType::CInt) |
+                    (DataType::CInt, DataType::I8) |
+                    (DataType::I16, DataType::CInt) |
+                    (DataType::CInt, DataType::I16) |
+                    (DataType::CInt, DataType::CInt) => Some(DataType::CInt),
+                    (DataType::ComptimeNumber, DataType::CLong) |
+                    (DataType::CLong, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CLong) |
+                    (DataType::CLong, DataType::U8) |
+                    (DataType::U16, DataType::CLong) |
+                    (DataType::CLong, DataType::U16) |
+                    (DataType::I8, DataType::CLong) |
+                    (DataType::CLong, DataType::I8) |
+                    (DataType::I16, DataType::CLong) |
+                    (DataType::CLong, DataType::I16) |
+                    (DataType::I32, DataType::CLong) |
+                    (DataType::CLong, DataType::I32) |
+                    (DataType::CLong, DataType::CLong) => Some(DataType::CLong),
                     _ => None,
                 }
             }
@@ -515,6 +906,54 @@ impl Operator {
                     (DataType::U32, DataType::I64) |
                     (DataType::I64, DataType::U32) |
                     (DataType::I64, DataType::I64) => Some(DataType::I64),
+                    (DataType::ComptimeNumber, DataType::Usize) |
+                    (DataType::Usize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Usize) |
+                    (DataType::Usize, DataType::U8) |
+                    (DataType::U16, DataType::Usize) |
+                    (DataType::Usize, DataType::U16) |
+                    (DataType::U32, DataType::Usize) |
+                    (DataType::Usize, DataType::U32) |
+                    (DataType::Usize, DataType::Usize) => Some(DataType::Usize),
+                    (DataType::ComptimeNumber, DataType::Isize) |
+                    (DataType::Isize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Isize) |
+                    (DataType::Isize, DataType::U8) |
+                    (DataType::U16, DataType::Isize) |
+                    (DataType::Isize, DataType::U16) |
+                    (DataType::U32, DataType::Isize) |
+                    (DataType::Isize, DataType::U32) |
+                    (DataType::I8, DataType::Isize) |
+                    (DataType::Isize, DataType::I8) |
+                    (DataType::I16, DataType::Isize) |
+                    (DataType::Isize, DataType::I16) |
+                    (DataType::I32, DataType::Isize) |
+                    (DataType::Isize, DataType::I32) |
+                    (DataType::Isize, DataType::Isize) => Some(DataType::Isize),
+                    (DataType::ComptimeNumber, DataType::CInt) |
+                    (DataType::CInt, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CInt) |
+                    (DataType::CInt, DataType::U8) |
+                    (DataType::U16, DataType::CInt) |
+                    (DataType::CInt, DataType::U16) |
+                    (DataType::I8, DataType::CInt) |
+                    (DataType::CInt, DataType::I8) |
+                    (DataType::I16, DataType::CInt) |
+                    (DataType::CInt, DataType::I16) |
+                    (DataType::CInt, DataType::CInt) => Some(DataType::CInt),
+                    (DataType::ComptimeNumber, DataType::CLong) |
+                    (DataType::CLong, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CLong) |
+                    (DataType::CLong, DataType::U8) |
+                    (DataType::U16, DataType::CLong) |
+                    (DataType::CLong, DataType::U16) |
+                    (DataType::I8, DataType::CLong) |
+                    (DataType::CLong, DataType::I8) |
+                    (DataType::I16, DataType::CLong) |
+                    (DataType::CLong, DataType::I16) |
+                    (DataType::I32, DataType::CLong) |
+                    (DataType::CLong, DataType::I32) |
+                    (DataType::CLong, DataType::CLong) => Some(DataType::CLong),
                     _ => None,
                 }
             }
@@ -569,6 +1008,54 @@ impl Operator {
                     (DataType::U32, DataType::I64) |
                     (DataType::I64, DataType::U32) |
                     (DataType::I64, DataType::I64) => Some(DataType::I64),
+                    (DataType::ComptimeNumber, DataType::Usize) |
+                    (DataType::Usize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Usize) |
+                    (DataType::Usize, DataType::U8) |
+                    (DataType::U16, DataType::Usize) |
+                    (DataType::Usize, DataType::U16) |
+                    (DataType::U32, DataType::Usize) |
+                    (DataType::Usize, DataType::U32) |
+                    (DataType::Usize, DataType::Usize) => Some(DataType::Usize),
+                    (DataType::ComptimeNumber, DataType::Isize) |
+                    (DataType::Isize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Isize) |
+                    (DataType::Isize, DataType::U8) |
+                    (DataType::U16, DataType::Isize) |
+                    (DataType::Isize, DataType::U16) |
+                    (DataType::U32, DataType::Isize) |
+                    (DataType::Isize, DataType::U32) |
+                    (DataType::I8, DataType::Isize) |
+                    (DataType::Isize, DataType::I8) |
+                    (DataType::I16, DataType::Isize) |
+                    (DataType::Isize, DataType::I16) |
+                    (DataType::I32, DataType::Isize) |
+                    (DataType::Isize, DataType::I32) |
+                    (DataType::Isize, DataType::Isize) => Some(DataType::Isize),
+                    (DataType::ComptimeNumber, DataType::CInt) |
+                    (DataType::CInt, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CInt) |
+                    (DataType::CInt, DataType::U8) |
+                    (DataType::U16, DataType::CInt) |
+                    (DataType::CInt, DataType::U16) |
+                    (DataType::I8, DataType::CInt) |
+                    (DataType::CInt, DataType::I8) |
+                    (DataType::I16, DataType::CInt) |
+                    (DataType::CInt, DataType::I16) |
+                    (DataType::CInt, DataType::CInt) => Some(DataType::CInt),
+                    (DataType::ComptimeNumber, DataType::CLong) |
+                    (DataType::CLong, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CLong) |
+                    (DataType::CLong, DataType::U8) |
+                    (DataType::U16, DataType::CLong) |
+                    (DataType::CLong, DataType::U16) |
+                    (DataType::I8, DataType::CLong) |
+                    (DataType::CLong, DataType::I8) |
+                    (DataType::I16, DataType::CLong) |
+                    (DataType::CLong, DataType::I16) |
+                    (DataType::I32, DataType::CLong) |
+                    (DataType::CLong, DataType::I32) |
+                    (DataType::CLong, DataType::CLong) => Some(DataType::CLong),
                     _ => None,
                 }
             }
@@ -623,6 +1110,54 @@ impl Operator {
                     (DataType::U32, DataType::I64) |
                     (DataType::I64, DataType::U32) |
                     (DataType::I64, DataType::I64) => Some(DataType::I64),
+                    (DataType::ComptimeNumber, DataType::Usize) |
+                    (DataType::Usize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Usize) |
+                    (DataType::Usize, DataType::U8) |
+                    (DataType::U16, DataType::Usize) |
+                    (DataType::Usize, DataType::U16) |
+                    (DataType::U32, DataType::Usize) |
+                    (DataType::Usize, DataType::U32) |
+                    (DataType::Usize, DataType::Usize) => Some(DataType::Usize),
+                    (DataType::ComptimeNumber, DataType::Isize) |
+                    (DataType::Isize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Isize) |
+                    (DataType::Isize, DataType::U8) |
+                    (DataType::U16, DataType::Isize) |
+                    (DataType::Isize, DataType::U16) |
+                    (DataType::U32, DataType::Isize) |
+                    (DataType::Isize, DataType::U32) |
+                    (DataType::I8, DataType::Isize) |
+                    (DataType::Isize, DataType::I8) |
+                    (DataType::I16, DataType::Isize) |
+                    (DataType::Isize, DataType::I16) |
+                    (DataType::I32, DataType::Isize) |
+                    (DataType::Isize, DataType::I32) |
+                    (DataType::Isize, DataType::Isize) => Some(DataType::Isize),
+                    (DataType::ComptimeNumber, DataType::CInt) |
+                    (DataType::CInt, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CInt) |
+                    (DataType::CInt, DataType::U8) |
+                    (DataType::U16, DataType::CInt) |
+                    (DataType::CInt, DataType::U16) |
+                    (DataType::I8, DataType::CInt) |
+                    (DataType::CInt, DataType::I8) |
+                    (DataType::I16, DataType::CInt) |
+                    (DataType::CInt, DataType::I16) |
+                    (DataType::CInt, DataType::CInt) => Some(DataType::CInt),
+                    (DataType::ComptimeNumber, DataType::CLong) |
+                    (DataType::CLong, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CLong) |
+                    (DataType::CLong, DataType::U8) |
+                    (DataType::U16, DataType::CLong) |
+                    (DataType::CLong, DataType::U16) |
+                    (DataType::I8, DataType::CLong) |
+                    (DataType::CLong, DataType::I8) |
+                    (DataType::I16, DataType::CLong) |
+                    (DataType::CLong, DataType::I16) |
+                    (DataType::I32, DataType::CLong) |
+                    (DataType::CLong, DataType::I32) |
+                    (DataType::CLong, DataType::CLong) => Some(DataType::CLong),
                     _ => None,
                 }
             }
@@ -677,6 +1212,54 @@ impl Operator {
                     (DataType::U32, DataType::I64) |
                     (DataType::I64, DataType::U32) |
                     (DataType::I64, DataType::I64) => Some(DataType::I64),
+                    (DataType::ComptimeNumber, DataType::Usize) |
+                    (DataType::Usize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Usize) |
+                    (DataType::Usize, DataType::U8) |
+                    (DataType::U16, DataType::Usize) |
+                    (DataType::Usize, DataType::U16) |
+                    (DataType::U32, DataType::Usize) |
+                    (DataType::Usize, DataType::U32) |
+                    (DataType::Usize, DataType::Usize) => Some(DataType::Usize),
+                    (DataType::ComptimeNumber, DataType::Isize) |
+                    (DataType::Isize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Isize) |
+                    (DataType::Isize, DataType::U8) |
+                    (DataType::U16, DataType::Isize) |
+                    (DataType::Isize, DataType::U16) |
+                    (DataType::U32, DataType::Isize) |
+                    (DataType::Isize, DataType::U32) |
+                    (DataType::I8, DataType::Isize) |
+                    (DataType::Isize, DataType::I8) |
+                    (DataType::I16, DataType::Isize) |
+                    (DataType::Isize, DataType::I16) |
+                    (DataType::I32, DataType::Isize) |
+                    (DataType::Isize, DataType::I32) |
+                    (DataType::Isize, DataType::Isize) => Some(DataType::Isize),
+                    (DataType::ComptimeNumber, DataType::CInt) |
+                    (DataType::CInt, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CInt) |
+                    (DataType::CInt, DataType::U8) |
+                    (DataType::U16, DataType::CInt) |
+                    (DataType::CInt, DataType::U16) |
+                    (DataType::I8, DataType::CInt) |
+                    (DataType::CInt, DataType::I8) |
+                    (DataType::I16, DataType::CInt) |
+                    (DataType::CInt, DataType::I16) |
+                    (DataType::CInt, DataType::CInt) => Some(DataType::CInt),
+                    (DataType::ComptimeNumber, DataType::CLong) |
+                    (DataType::CLong, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CLong) |
+                    (DataType::CLong, DataType::U8) |
+                    (DataType::U16, DataType::CLong) |
+                    (DataType::CLong, DataType::U16) |
+                    (DataType::I8, DataType::CLong) |
+                    (DataType::CLong, DataType::I8) |
+                    (DataType::I16, DataType::CLong) |
+                    (DataType::CLong, DataType::I16) |
+                    (DataType::I32, DataType::CLong) |
+                    (DataType::CLong, DataType::I32) |
+                    (DataType::CLong, DataType::CLong) => Some(DataType::CLong),
                     _ => None,
                 }
             }
@@ -765,7 +1348,71 @@ impl Operator {
                     (DataType::I64, DataType::I64) |
                     (DataType::ComptimeChar, DataType::ComptimeNumber) |
                     (DataType::ComptimeNumber, DataType::ComptimeChar) |
+                    (DataType::ComptimeString, DataType::ComptimeString) |
                     (DataType::ComptimeChar, DataType::ComptimeChar) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeNumber, DataType::Usize) |
+                    (DataType::Usize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Usize) |
+                    (DataType::Usize, DataType::U8) |
+                    (DataType::U16, DataType::Usize) |
+                    (DataType::Usize, DataType::U16) |
+                    (DataType::U32, DataType::Usize) |
+                    (DataType::Usize, DataType::U32) |
+                    (DataType::Usize, DataType::Usize) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeNumber, DataType::Isize) |
+                    (DataType::Isize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Isize) |
+                    (DataType::Isize, DataType::U8) |
+                    (DataType::U16, DataType::Isize) |
+                    (DataType::Isize, DataType::U16) |
+                    (DataType::U32, DataType::Isize) |
+                    (DataType::Isize, DataType::U32) |
+                    (DataType::I8, DataType::Isize) |
+                    (DataType::Isize, DataType::I8) |
+                    (DataType::I16, DataType::Isize) |
+                    (DataType::Isize, DataType::I16) |
+                    (DataType::I32, DataType::Isize) |
+                    (DataType::Isize, DataType::I32) |
+                    (DataType::Isize, DataType::Isize) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeNumber, DataType::CInt) |
+                    (DataType::CInt, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CInt) |
+                    (DataType::CInt, DataType::U8) |
+                    (DataType::U16, DataType::CInt) |
+                    (DataType::CInt, DataType::U16) |
+                    (DataType::I8, DataType::CInt) |
+                    (DataType::CInt, DataType::I8) |
+                    (DataType::I16, DataType::CInt) |
+                    (DataType::CInt, DataType::I16) |
+                    (DataType::CInt, DataType::CInt) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeNumber, DataType::CLong) |
+                    (DataType::CLong, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CLong) |
+                    (DataType::CLong, DataType::U8) |
+                    (DataType::U16, DataType::CLong) |
+                    (DataType::CLong, DataType::U16) |
+                    (DataType::I8, DataType::CLong) |
+                    (DataType::CLong, DataType::I8) |
+                    (DataType::I16, DataType::CLong) |
+                    (DataType::CLong, DataType::I16) |
+                    (DataType::I32, DataType::CLong) |
+                    (DataType::CLong, DataType::I32) |
+                    (DataType::CLong, DataType::CLong) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeFloat, DataType::ComptimeFloat) |
+                    (DataType::ComptimeFloat, DataType::ComptimeNumber) |
+                    (DataType::ComptimeNumber, DataType::ComptimeFloat) |
+                    (DataType::ComptimeNumber, DataType::F32) |
+                    (DataType::ComptimeFloat, DataType::F32) |
+                    (DataType::F32, DataType::ComptimeNumber) |
+                    (DataType::F32, DataType::ComptimeFloat) |
+                    (DataType::F32, DataType::F32) |
+                    (DataType::ComptimeNumber, DataType::F64) |
+                    (DataType::ComptimeFloat, DataType::F64) |
+                    (DataType::F64, DataType::ComptimeNumber) |
+                    (DataType::F64, DataType::ComptimeFloat) |
+                    (DataType::F32, DataType::F64) |
+                    (DataType::F64, DataType::F32) |
+                    (DataType::F64, DataType::F64) => Some(DataType::ComptimeBool),
                     _ => None,
                 }
             }
@@ -854,7 +1501,71 @@ impl Operator {
                     (DataType::I64, DataType::I64) |
                     (DataType::ComptimeChar, DataType::ComptimeNumber) |
                     (DataType::ComptimeNumber, DataType::ComptimeChar) |
+                    (DataType::ComptimeString, DataType::ComptimeString) |
                     (DataType::ComptimeChar, DataType::ComptimeChar) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeNumber, DataType::Usize) |
+                    (DataType::Usize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Usize) |
+                    (DataType::Usize, DataType::U8) |
+                    (DataType::U16, DataType::Usize) |
+                    (DataType::Usize, DataType::U16) |
+                    (DataType::U32, DataType::Usize) |
+                    (DataType::Usize, DataType::U32) |
+                    (DataType::Usize, DataType::Usize) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeNumber, DataType::Isize) |
+                    (DataType::Isize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Isize) |
+                    (DataType::Isize, DataType::U8) |
+                    (DataType::U16, DataType::Isize) |
+                    (DataType::Isize, DataType::U16) |
+                    (DataType::U32, DataType::Isize) |
+                    (DataType::Isize, DataType::U32) |
+                    (DataType::I8, DataType::Isize) |
+                    (DataType::Isize, DataType::I8) |
+                    (DataType::I16, DataType::Isize) |
+                    (DataType::Isize, DataType::I16) |
+                    (DataType::I32, DataType::Isize) |
+                    (DataType::Isize, DataType::I32) |
+                    (DataType::Isize, DataType::Isize) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeNumber, DataType::CInt) |
+                    (DataType::CInt, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CInt) |
+                    (DataType::CInt, DataType::U8) |
+                    (DataType::U16, DataType::CInt) |
+                    (DataType::CInt, DataType::U16) |
+                    (DataType::I8, DataType::CInt) |
+                    (DataType::CInt, DataType::I8) |
+                    (DataType::I16, DataType::CInt) |
+                    (DataType::CInt, DataType::I16) |
+                    (DataType::CInt, DataType::CInt) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeNumber, DataType::CLong) |
+                    (DataType::CLong, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CLong) |
+                    (DataType::CLong, DataType::U8) |
+                    (DataType::U16, DataType::CLong) |
+                    (DataType::CLong, DataType::U16) |
+                    (DataType::I8, DataType::CLong) |
+                    (DataType::CLong, DataType::I8) |
+                    (DataType::I16, DataType::CLong) |
+                    (DataType::CLong, DataType::I16) |
+                    (DataType::I32, DataType::CLong) |
+                    (DataType::CLong, DataType::I32) |
+                    (DataType::CLong, DataType::CLong) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeFloat, DataType::ComptimeFloat) |
+                    (DataType::ComptimeFloat, DataType::ComptimeNumber) |
+                    (DataType::ComptimeNumber, DataType::ComptimeFloat) |
+                    (DataType::ComptimeNumber, DataType::F32) |
+                    (DataType::ComptimeFloat, DataType::F32) |
+                    (DataType::F32, DataType::ComptimeNumber) |
+                    (DataType::F32, DataType::ComptimeFloat) |
+                    (DataType::F32, DataType::F32) |
+                    (DataType::ComptimeNumber, DataType::F64) |
+                    (DataType::ComptimeFloat, DataType::F64) |
+                    (DataType::F64, DataType::ComptimeNumber) |
+                    (DataType::F64, DataType::ComptimeFloat) |
+                    (DataType::F32, DataType::F64) |
+                    (DataType::F64, DataType::F32) |
+                    (DataType::F64, DataType::F64) => Some(DataType::ComptimeBool),
                     _ => None,
                 }
             }
@@ -943,7 +1654,71 @@ impl Operator {
                     (DataType::I64, DataType::I64) |
                     (DataType::ComptimeChar, DataType::ComptimeNumber) |
                     (DataType::ComptimeNumber, DataType::ComptimeChar) |
+                    (DataType::ComptimeString, DataType::ComptimeString) |
                     (DataType::ComptimeChar, DataType::ComptimeChar) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeNumber, DataType::Usize) |
+                    (DataType::Usize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Usize) |
+                    (DataType::Usize, DataType::U8) |
+                    (DataType::U16, DataType::Usize) |
+                    (DataType::Usize, DataType::U16) |
+                    (DataType::U32, DataType::Usize) |
+                    (DataType::Usize, DataType::U32) |
+                    (DataType::Usize, DataType::Usize) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeNumber, DataType::Isize) |
+                    (DataType::Isize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Isize) |
+                    (DataType::Isize, DataType::U8) |
+                    (DataType::U16, DataType::Isize) |
+                    (DataType::Isize, DataType::U16) |
+                    (DataType::U32, DataType::Isize) |
+                    (DataType::Isize, DataType::U32) |
+                    (DataType::I8, DataType::Isize) |
+                    (DataType::Isize, DataType::I8) |
+                    (DataType::I16, DataType::Isize) |
+                    (DataType::Isize, DataType::I16) |
+                    (DataType::I32, DataType::Isize) |
+                    (DataType::Isize, DataType::I32) |
+                    (DataType::Isize, DataType::Isize) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeNumber, DataType::CInt) |
+                    (DataType::CInt, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CInt) |
+                    (DataType::CInt, DataType::U8) |
+                    (DataType::U16, DataType::CInt) |
+                    (DataType::CInt, DataType::U16) |
+                    (DataType::I8, DataType::CInt) |
+                    (DataType::CInt, DataType::I8) |
+                    (DataType::I16, DataType::CInt) |
+                    (DataType::CInt, DataType::I16) |
+                    (DataType::CInt, DataType::CInt) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeNumber, DataType::CLong) |
+                    (DataType::CLong, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CLong) |
+                    (DataType::CLong, DataType::U8) |
+                    (DataType::U16, DataType::CLong) |
+                    (DataType::CLong, DataType::U16) |
+                    (DataType::I8, DataType::CLong) |
+                    (DataType::CLong, DataType::I8) |
+                    (DataType::I16, DataType::CLong) |
+                    (DataType::CLong, DataType::I16) |
+                    (DataType::I32, DataType::CLong) |
+                    (DataType::CLong, DataType::I32) |
+                    (DataType::CLong, DataType::CLong) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeFloat, DataType::ComptimeFloat) |
+                    (DataType::ComptimeFloat, DataType::ComptimeNumber) |
+                    (DataType::ComptimeNumber, DataType::ComptimeFloat) |
+                    (DataType::ComptimeNumber, DataType::F32) |
+                    (DataType::ComptimeFloat, DataType::F32) |
+                    (DataType::F32, DataType::ComptimeNumber) |
+                    (DataType::F32, DataType::ComptimeFloat) |
+                    (DataType::F32, DataType::F32) |
+                    (DataType::ComptimeNumber, DataType::F64) |
+                    (DataType::ComptimeFloat, DataType::F64) |
+                    (DataType::F64, DataType::ComptimeNumber) |
+                    (DataType::F64, DataType::ComptimeFloat) |
+                    (DataType::F32, DataType::F64) |
+                    (DataType::F64, DataType::F32) |
+                    (DataType::F64, DataType::F64) => Some(DataType::ComptimeBool),
                     _ => None,
                 }
             }
@@ -1032,7 +1807,71 @@ impl Operator {
                     (DataType::I64, DataType::I64) |
                     (DataType::ComptimeChar, DataType::ComptimeNumber) |
                     (DataType::ComptimeNumber, DataType::ComptimeChar) |
+                    (DataType::ComptimeString, DataType::ComptimeString) |
                     (DataType::ComptimeChar, DataType::ComptimeChar) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeNumber, DataType::Usize) |
+                    (DataType::Usize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Usize) |
+                    (DataType::Usize, DataType::U8) |
+                    (DataType::U16, DataType::Usize) |
+                    (DataType::Usize, DataType::U16) |
+                    (DataType::U32, DataType::Usize) |
+                    (DataType::Usize, DataType::U32) |
+                    (DataType::Usize, DataType::Usize) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeNumber, DataType::Isize) |
+                    (DataType::Isize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Isize) |
+                    (DataType::Isize, DataType::U8) |
+                    (DataType::U16, DataType::Isize) |
+                    (DataType::Isize, DataType::U16) |
+                    (DataType::U32, DataType::Isize) |
+                    (DataType::Isize, DataType::U32) |
+                    (DataType::I8, DataType::Isize) |
+                    (DataType::Isize, DataType::I8) |
+                    (DataType::I16, DataType::Isize) |
+                    (DataType::Isize, DataType::I16) |
+                    (DataType::I32, DataType::Isize) |
+                    (DataType::Isize, DataType::I32) |
+                    (DataType::Isize, DataType::Isize) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeNumber, DataType::CInt) |
+                    (DataType::CInt, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CInt) |
+                    (DataType::CInt, DataType::U8) |
+                    (DataType::U16, DataType::CInt) |
+                    (DataType::CInt, DataType::U16) |
+                    (DataType::I8, DataType::CInt) |
+                    (DataType::CInt, DataType::I8) |
+                    (DataType::I16, DataType::CInt) |
+                    (DataType::CInt, DataType::I16) |
+                    (DataType::CInt, DataType::CInt) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeNumber, DataType::CLong) |
+                    (DataType::CLong, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CLong) |
+                    (DataType::CLong, DataType::U8) |
+                    (DataType::U16, DataType::CLong) |
+                    (DataType::CLong, DataType::U16) |
+                    (DataType::I8, DataType::CLong) |
+                    (DataType::CLong, DataType::I8) |
+                    (DataType::I16, DataType::CLong) |
+                    (DataType::CLong, DataType::I16) |
+                    (DataType::I32, DataType::CLong) |
+                    (DataType::CLong, DataType::I32) |
+                    (DataType::CLong, DataType::CLong) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeFloat, DataType::ComptimeFloat) |
+                    (DataType::ComptimeFloat, DataType::ComptimeNumber) |
+                    (DataType::ComptimeNumber, DataType::ComptimeFloat) |
+                    (DataType::ComptimeNumber, DataType::F32) |
+                    (DataType::ComptimeFloat, DataType::F32) |
+                    (DataType::F32, DataType::ComptimeNumber) |
+                    (DataType::F32, DataType::ComptimeFloat) |
+                    (DataType::F32, DataType::F32) |
+                    (DataType::ComptimeNumber, DataType::F64) |
+                    (DataType::ComptimeFloat, DataType::F64) |
+                    (DataType::F64, DataType::ComptimeNumber) |
+                    (DataType::F64, DataType::ComptimeFloat) |
+                    (DataType::F32, DataType::F64) |
+                    (DataType::F64, DataType::F32) |
+                    (DataType::F64, DataType::F64) => Some(DataType::ComptimeBool),
                     _ => None,
                 }
             }
@@ -1121,7 +1960,72 @@ impl Operator {
                     (DataType::I64, DataType::I64) |
                     (DataType::ComptimeChar, DataType::ComptimeNumber) |
                     (DataType::ComptimeNumber, DataType::ComptimeChar) |
+                    (DataType::ComptimeString, DataType::ComptimeString) |
                     (DataType::ComptimeChar, DataType::ComptimeChar) => Some(DataType::ComptimeBool),
+                    (DataType::Enum(left), DataType::Enum(right)) if left == right => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeNumber, DataType::Usize) |
+                    (DataType::Usize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Usize) |
+                    (DataType::Usize, DataType::U8) |
+                    (DataType::U16, DataType::Usize) |
+                    (DataType::Usize, DataType::U16) |
+                    (DataType::U32, DataType::Usize) |
+                    (DataType::Usize, DataType::U32) |
+                    (DataType::Usize, DataType::Usize) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeNumber, DataType::Isize) |
+                    (DataType::Isize, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::Isize) |
+                    (DataType::Isize, DataType::U8) |
+                    (DataType::U16, DataType::Isize) |
+                    (DataType::Isize, DataType::U16) |
+                    (DataType::U32, DataType::Isize) |
+                    (DataType::Isize, DataType::U32) |
+                    (DataType::I8, DataType::Isize) |
+                    (DataType::Isize, DataType::I8) |
+                    (DataType::I16, DataType::Isize) |
+                    (DataType::Isize, DataType::I16) |
+                    (DataType::I32, DataType::Isize) |
+                    (DataType::Isize, DataType::I32) |
+                    (DataType::Isize, DataType::Isize) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeNumber, DataType::CInt) |
+                    (DataType::CInt, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CInt) |
+                    (DataType::CInt, DataType::U8) |
+                    (DataType::U16, DataType::CInt) |
+                    (DataType::CInt, DataType::U16) |
+                    (DataType::I8, DataType::CInt) |
+                    (DataType::CInt, DataType::I8) |
+                    (DataType::I16, DataType::CInt) |
+                    (DataType::CInt, DataType::I16) |
+                    (DataType::CInt, DataType::CInt) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeNumber, DataType::CLong) |
+                    (DataType::CLong, DataType::ComptimeNumber) |
+                    (DataType::U8, DataType::CLong) |
+                    (DataType::CLong, DataType::U8) |
+                    (DataType::U16, DataType::CLong) |
+                    (DataType::CLong, DataType::U16) |
+                    (DataType::I8, DataType::CLong) |
+                    (DataType::CLong, DataType::I8) |
+                    (DataType::I16, DataType::CLong) |
+                    (DataType::CLong, DataType::I16) |
+                    (DataType::I32, DataType::CLong) |
+                    (DataType::CLong, DataType::I32) |
+                    (DataType::CLong, DataType::CLong) => Some(DataType::ComptimeBool),
+                    (DataType::ComptimeFloat, DataType::ComptimeFloat) |
+                    (DataType::ComptimeFloat, DataType::ComptimeNumber) |
+                    (DataType::ComptimeNumber, DataType::ComptimeFloat) |
+                    (DataType::ComptimeNumber, DataType::F32) |
+                    (DataType::ComptimeFloat, DataType::F32) |
+                    (DataType::F32, DataType::ComptimeNumber) |
+                    (DataType::F32, DataType::ComptimeFloat) |
+                    (DataType::F32, DataType::F32) |
+                    (DataType::ComptimeNumber, DataType::F64) |
+                    (DataType::ComptimeFloat, DataType::F64) |
+                    (DataType::F64, DataType::ComptimeNumber) |
+                    (DataType::F64, DataType::ComptimeFloat) |
+                    (DataType::F32, DataType::F64) |
+                    (DataType::F64, DataType::F32) |
+                    (DataType::F64, DataType::F64) => Some(DataType::ComptimeBool),
                     _ => None,
                 }
             }
@@ -1212,6 +2116,70 @@ impl Operator {
                 (DataType::ComptimeBool, DataType::ComptimeBool) |
                 (DataType::ComptimeString, DataType::ComptimeString) |
                 (DataType::ComptimeChar, DataType::ComptimeChar) => Some(DataType::ComptimeBool),
+                (DataType::Enum(left), DataType::Enum(right)) if left == right => Some(DataType::ComptimeBool),
+                (DataType::ComptimeNumber, DataType::Usize) |
+                (DataType::Usize, DataType::ComptimeNumber) |
+                (DataType::U8, DataType::Usize) |
+                (DataType::Usize, DataType::U8) |
+                (DataType::U16, DataType::Usize) |
+                (DataType::Usize, DataType::U16) |
+                (DataType::U32, DataType::Usize) |
+                (DataType::Usize, DataType::U32) |
+                (DataType::Usize, DataType::Usize) => Some(DataType::ComptimeBool),
+                (DataType::ComptimeNumber, DataType::Isize) |
+                (DataType::Isize, DataType::ComptimeNumber) |
+                (DataType::U8, DataType::Isize) |
+                (DataType::Isize, DataType::U8) |
+                (DataType::U16, DataType::Isize) |
+                (DataType::Isize, DataType::U16) |
+                (DataType::U32, DataType::Isize) |
+                (DataType::Isize, DataType::U32) |
+                (DataType::I8, DataType::Isize) |
+                (DataType::Isize, DataType::I8) |
+                (DataType::I16, DataType::Isize) |
+                (DataType::Isize, DataType::I16) |
+                (DataType::I32, DataType::Isize) |
+                (DataType::Isize, DataType::I32) |
+                (DataType::Isize, DataType::Isize) => Some(DataType::ComptimeBool),
+                (DataType::ComptimeNumber, DataType::CInt) |
+                (DataType::CInt, DataType::ComptimeNumber) |
+                (DataType::U8, DataType::CInt) |
+                (DataType::CInt, DataType::U8) |
+                (DataType::U16, DataType::CInt) |
+                (DataType::CInt, DataType::U16) |
+                (DataType::I8, DataType::CInt) |
+                (DataType::CInt, DataType::I8) |
+                (DataType::I16, DataType::CInt) |
+                (DataType::CInt, DataType::I16) |
+                (DataType::CInt, DataType::CInt) => Some(DataType::ComptimeBool),
+                (DataType::ComptimeNumber, DataType::CLong) |
+                (DataType::CLong, DataType::ComptimeNumber) |
+                (DataType::U8, DataType::CLong) |
+                (DataType::CLong, DataType::U8) |
+                (DataType::U16, DataType::CLong) |
+                (DataType::CLong, DataType::U16) |
+                (DataType::I8, DataType::CLong) |
+                (DataType::CLong, DataType::I8) |
+                (DataType::I16, DataType::CLong) |
+                (DataType::CLong, DataType::I16) |
+                (DataType::I32, DataType::CLong) |
+                (DataType::CLong, DataType::I32) |
+                (DataType::CLong, DataType::CLong) => Some(DataType::ComptimeBool),
+                (DataType::ComptimeFloat, DataType::ComptimeFloat) |
+                (DataType::ComptimeFloat, DataType::ComptimeNumber) |
+                (DataType::ComptimeNumber, DataType::ComptimeFloat) |
+                (DataType::ComptimeNumber, DataType::F32) |
+                (DataType::ComptimeFloat, DataType::F32) |
+                (DataType::F32, DataType::ComptimeNumber) |
+                (DataType::F32, DataType::ComptimeFloat) |
+                (DataType::F32, DataType::F32) |
+                (DataType::ComptimeNumber, DataType::F64) |
+                (DataType::ComptimeFloat, DataType::F64) |
+                (DataType::F64, DataType::ComptimeNumber) |
+                (DataType::F64, DataType::ComptimeFloat) |
+                (DataType::F32, DataType::F64) |
+                (DataType::F64, DataType::F32) |
+                (DataType::F64, DataType::F64) => Some(DataType::ComptimeBool),
                 _ => None,
             }
             }
@@ -1245,6 +2213,7 @@ impl Operator {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataType {
     ComptimeNumber,
+    ComptimeFloat,
     ComptimeString,
     ComptimeChar,
     ComptimeBool,
@@ -1256,19 +2225,42 @@ pub enum DataType {
     I16,
     I32,
     I64,
+    Usize,
+    Isize,
+    CInt,
+    CLong,
+    F32,
+    F64,
     String,
     Bool,
     Char,
     Ref(Box<Positioned<DataType>>),
     ConstRef(Box<Positioned<DataType>>),
     Void,
+    Custom(String),
+    Struct(String),
+    Enum(String),
+    Union(String),
+    Array(Box<Positioned<DataType>>, usize),
+    Slice(Box<Positioned<DataType>>),
+    Function(Vec<Positioned<DataType>>, Box<Positioned<DataType>>),
+    // Name of a type alias and the type it names, only kept in declarations for the generated C
+    Alias(String, Box<Positioned<DataType>>),
 }
 
 impl DataType {
 
+    pub fn unaliased(&self) -> DataType {
+        return match self {
+            DataType::Alias(_, inner) => inner.data.unaliased(),
+            _ => self.clone(),
+        }
+    }
+
     pub fn is_comptime(&self) -> bool {
         return match self {
             DataType::ComptimeNumber => true,
+            DataType::ComptimeFloat => true,
             DataType::ComptimeString => true,
             DataType::ComptimeChar => true,
             DataType::ComptimeBool => true,
@@ -1276,6 +2268,112 @@ impl DataType {
         }
     }
 
+    pub fn default_type(&self) -> DataType {
+        // The type a literal takes when nothing else constrains it
+        return match self {
+            DataType::ComptimeNumber => DataType::I32,
+            DataType::ComptimeFloat => DataType::F64,
+            DataType::ComptimeString => DataType::String,
+            DataType::ComptimeChar => DataType::Char,
+            DataType::ComptimeBool => DataType::Bool,
+            _ => self.clone()
+        }
+    }
+
+    pub fn is_integer(&self) -> bool {
+        return match self {
+            DataType::U8 => true,
+            DataType::U16 => true,
+            DataType::U32 => true,
+            DataType::U64 => true,
+            DataType::I8 => true,
+            DataType::I16 => true,
+            DataType::I32 => true,
+            DataType::I64 => true,
+            DataType::Usize => true,
+            DataType::Isize => true,
+            DataType::CInt => true,
+            DataType::CLong => true,
+            _ => false
+        }
+    }
+
+    pub fn is_float(&self) -> bool {
+        return match self {
+            DataType::F32 => true,
+            DataType::F64 => true,
+            _ => false
+        }
+    }
+
+    pub fn integer_range(&self) -> Option<(i128, i128)> {
+        return match self {
+            DataType::U8 => Some((u8::MIN as i128, u8::MAX as i128)),
+            DataType::U16 => Some((u16::MIN as i128, u16::MAX as i128)),
+            DataType::U32 => Some((u32::MIN as i128, u32::MAX as i128)),
+            DataType::U64 => Some((u64::MIN as i128, u64::MAX as i128)),
+            DataType::I8 => Some((i8::MIN as i128, i8::MAX as i128)),
+            DataType::I16 => Some((i16::MIN as i128, i16::MAX as i128)),
+            DataType::I32 => Some((i32::MIN as i128, i32::MAX as i128)),
+            DataType::I64 => Some((i64::MIN as i128, i64::MAX as i128)),
+            // Sizes of the 64-bit Linux targets
+            DataType::Usize => Some((u64::MIN as i128, u64::MAX as i128)),
+            DataType::Isize => Some((i64::MIN as i128, i64::MAX as i128)),
+            DataType::CInt => Some((i32::MIN as i128, i32::MAX as i128)),
+            DataType::CLong => Some((i64::MIN as i128, i64::MAX as i128)),
+            _ => None
+        }
+    }
+
+    pub fn literal_range(&self) -> Option<(i128, i128)> {
+        // Chars also take integer literals, holding the value of a byte
+        return match self {
+            DataType::Char => Some((u8::MIN as i128, u8::MAX as i128)),
+            data_type => data_type.integer_range(),
+        }
+    }
+
+    pub fn mangle(&self) -> String {
+        return match self {
+            DataType::ComptimeNumber => "comptime_number".to_string(),
+            DataType::ComptimeFloat => "comptime_float".to_string(),
+            DataType::ComptimeString => "comptime_str".to_string(),
+            DataType::ComptimeChar => "comptime_char".to_string(),
+            DataType::ComptimeBool => "comptime_bool".to_string(),
+            DataType::U8 => "u8".to_string(),
+            DataType::U16 => "u16".to_string(),
+            DataType::U32 => "u32".to_string(),
+            DataType::U64 => "u64".to_string(),
+            DataType::I8 => "i8".to_string(),
+            DataType::I16 => "i16".to_string(),
+            DataType::I32 => "i32".to_string(),
+            DataType::I64 => "i64".to_string(),
+            DataType::Usize => "usize".to_string(),
+            DataType::Isize => "isize".to_string(),
+            DataType::CInt => "c_int".to_string(),
+            DataType::CLong => "c_long".to_string(),
+            DataType::F32 => "f32".to_string(),
+            DataType::F64 => "f64".to_string(),
+            DataType::String => "str".to_string(),
+            DataType::Bool => "bool".to_string(),
+            DataType::Char => "char".to_string(),
+            DataType::Ref(inner) => format!("ref_{}", inner.data.mangle()),
+            DataType::ConstRef(inner) => format!("constref_{}", inner.data.mangle()),
+            DataType::Void => "void".to_string(),
+            DataType::Custom(name) => name.clone(),
+            DataType::Struct(name) => name.clone(),
+            DataType::Enum(name) => name.clone(),
+            DataType::Union(name) => name.clone(),
+            DataType::Array(inner, size) => format!("array{}_{}", size, inner.data.mangle()),
+            DataType::Slice(inner) => format!("slice_{}", inner.data.mangle()),
+            DataType::Function(params, return_type) => {
+                let params: Vec<String> = params.iter().map(|param| param.data.mangle()).collect();
+                format!("fn_{}_ret_{}", params.join("_"), return_type.data.mangle())
+            }
+            DataType::Alias(_, inner) => inner.data.mangle(),
+        }
+    }
+
     pub fn is_convertible(&self, other: DataType) -> bool {
         if *self == other {
             return true;
@@ -1289,14 +2387,36 @@ impl DataType {
             (DataType::ComptimeNumber, DataType::I16) |
             (DataType::ComptimeNumber, DataType::I32) |
             (DataType::ComptimeNumber, DataType::I64) |
+            (DataType::ComptimeNumber, DataType::Usize) |
+            (DataType::ComptimeNumber, DataType::Isize) |
+            (DataType::ComptimeNumber, DataType::CInt) |
+            (DataType::ComptimeNumber, DataType::CLong) |
             (DataType::ComptimeNumber, DataType::ComptimeChar) |
-            (DataType::ComptimeNumber, DataType::Char) => true,
+            (DataType::ComptimeNumber, DataType::Char) |
+            (DataType::ComptimeNumber, DataType::ComptimeFloat) |
+            (DataType::ComptimeNumber, DataType::F32) |
+            (DataType::ComptimeNumber, DataType::F64) => true,
+            (DataType::ComptimeFloat, DataType::F32) |
+            (DataType::ComptimeFloat, DataType::F64) |
+            (DataType::F32, DataType::F64) => true,
             (DataType::ComptimeString, DataType::String) => true,
             (DataType::ComptimeBool, DataType::Bool) => true,
             (DataType::ComptimeChar, DataType::Char) => true,
             (DataType::Ref(inner1), DataType::Ref(inner2)) => {
                 return inner1.data == inner2.data;
             }
+            (DataType::Array(inner1, size1), DataType::Array(inner2, size2)) => {
+                return *size1 == size2 && inner1.data.is_convertible(inner2.data);
+            }
+            (DataType::Array(inner1, _), DataType::Slice(inner2)) => {
+                return inner1.data.is_convertible(inner2.data);
+            }
+            (DataType::Ref(inner1), DataType::Slice(inner2)) => {
+                if let DataType::Array(array_inner, _) = &inner1.data {
+                    return array_inner.data == inner2.data;
+                }
+                return false;
+            }
             _ => false,
         }
     }
@@ -1314,13 +2434,31 @@ impl DataType {
             (DataType::ComptimeNumber, DataType::I16) |
             (DataType::ComptimeNumber, DataType::I32) |
             (DataType::ComptimeNumber, DataType::I64) |
+            (DataType::ComptimeNumber, DataType::Usize) |
+            (DataType::ComptimeNumber, DataType::Isize) |
+            (DataType::ComptimeNumber, DataType::CInt) |
+            (DataType::ComptimeNumber, DataType::CLong) |
             (DataType::ComptimeNumber, DataType::ComptimeChar) |
-            (DataType::ComptimeNumber, DataType::Char) => true,
+            (DataType::ComptimeNumber, DataType::Char) |
+            (DataType::ComptimeNumber, DataType::ComptimeFloat) |
+            (DataType::ComptimeNumber, DataType::F32) |
+            (DataType::ComptimeNumber, DataType::F64) => true,
+            (DataType::ComptimeFloat, DataType::F32) |
+            (DataType::ComptimeFloat, DataType::F64) => true,
+            (left, right) if left.is_integer() && right.is_integer() => true,
             (DataType::ComptimeString, DataType::String) => true,
             (DataType::ComptimeBool, DataType::Bool) => true,
             (DataType::ComptimeChar, DataType::Char) => true,
+            (DataType::ComptimeFloat, data_type) if data_type.is_integer() => true,
+            (left, right) if left.is_float() && (right.is_float() || right.is_integer()) => true,
+            (left, right) if left.is_integer() && right.is_float() => true,
+            (DataType::String, DataType::ConstRef(inner)) => inner.data == DataType::Char,
+            (DataType::String, _) => false,
             (DataType::Ref(inner), _) if inner.data == DataType::Void => true,
             (_, DataType::Ref(inner)) if inner.data == DataType::Void => true,
+            (DataType::ConstRef(inner), DataType::ConstRef(_)) if inner.data == DataType::Void => true,
+            (DataType::Ref(_) | DataType::ConstRef(_), DataType::ConstRef(inner)) if inner.data == DataType::Void => true,
+            (DataType::Enum(_), data_type) if data_type.is_integer() => true,
             _ => false,
         }
     }
@@ -1331,6 +2469,7 @@ impl From<ValueNode> for DataType {
     fn from(node: ValueNode) -> Self {
         return match node {
             ValueNode::Number(_) => Self::ComptimeNumber,
+            ValueNode::Float(_) => Self::ComptimeFloat,
             ValueNode::String(_) => Self::ComptimeString,
             ValueNode::Char(_) => Self::ComptimeChar,
             ValueNode::Boolean(_) => Self::ComptimeBool,
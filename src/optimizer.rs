@@ -3,7 +3,12 @@ use std::fmt::{Display, Formatter};
 use std::num::ParseIntError;
 use std::process::exit;
 use crate::{Lexer, Node, Parser, Positioned};
-use crate::node::{CompilerInstruction, DataType, Operator, VarType};
+use crate::bigint::BigInt;
+use crate::interpreter::{ComptimeFunction, Interpreter};
+use crate::node::{CompilerInstruction, DataType, Operator, ValueNode, VarType};
+
+// Largest shift amount accepted on comptime integers, keeps results at a reasonable size
+const MAX_COMPTIME_SHIFT: usize = 4096;
 
 pub enum OptimizerError {
     IncompatibleBinOperator(DataType, Operator, DataType),
@@ -11,7 +16,9 @@ pub enum OptimizerError {
     InvalidNumber(String, ParseIntError),
     IncompatibleTypes(DataType, DataType),
     MissingType,
-    Shadowing(String),
+    VoidValue,
+    VariableAlreadyExists(String),
+    ReservedName(String),
     VariableNotFound(String),
     VariableCannotBeModified(String),
     FunctionNotFound(String),
@@ -20,8 +27,47 @@ pub enum OptimizerError {
     CannotReturn,
     IncorrectParameterCount(usize, usize),
     DuplicateFunctionParameter(String),
+    DuplicateTypeParameter(String),
+    TypeParameterNotInferred(String),
+    InvalidInstantiation(String, Vec<DataType>, Box<OptimizerError>),
+    MethodNotFound(DataType, String),
+    NotAFunctionValue(String),
+    ReturnInDefer,
+    DeferNotAllowed,
+    InvalidReceiver(String, DataType),
     MissingMainFunction,
     MainFunctionNotCorrectlyDefined,
+    OutsideOfLoop(String),
+    TypeNotFound(String),
+    TypeAlreadyExists(String),
+    TypeDefinitionNotAllowed,
+    RecursiveType(String),
+    RecursiveAlias(String),
+    DuplicateField(String),
+    FieldNotFound(DataType, String),
+    MissingField(String),
+    NotAssignable,
+    ConstReference,
+    ComptimeReference,
+    DuplicateVariant(String),
+    VariantOverflow(String, i128, DataType),
+    ComptimeValueRequired,
+    ComptimeUnsupported(String),
+    ComptimeStepLimit(usize),
+    ComptimeRecursionLimit(usize),
+    IntegerOutOfRange(String, DataType, i128, i128),
+    FloatOutOfRange(String, DataType),
+    DivisionByZero,
+    ShiftOverflow(String),
+    InvalidMatch(DataType),
+    InvalidSwitch(DataType),
+    InvalidCaseRange(DataType),
+    OverlappingCase(String),
+    NonExhaustiveMatch(Vec<String>),
+    IndexOutOfBounds(i128, usize),
+    InvalidRange(i128, i128),
+    NotIndexable(DataType),
+    ArrayCopy,
 }
 
 impl Display for OptimizerError {
@@ -42,8 +88,14 @@ impl Display for OptimizerError {
             OptimizerError::MissingType => {
                 write!(f, "Missing type")?;
             }
-            OptimizerError::Shadowing(variable) => {
-                write!(f, "Shadowing of variable '{}'", variable)?;
+            OptimizerError::VoidValue => {
+                write!(f, "Expected a value, found 'Void'")?;
+            }
+            OptimizerError::VariableAlreadyExists(variable) => {
+                write!(f, "Variable '{}' already exists in this scope", variable)?;
+            }
+            OptimizerError::ReservedName(name) => {
+                write!(f, "Name '{}' is reserved, names cannot contain '__'", name)?;
             }
             OptimizerError::VariableNotFound(variable) => {
                 write!(f, "Variable '{}' not found", variable)?;
@@ -73,12 +125,153 @@ impl Display for OptimizerError {
             OptimizerError::DuplicateFunctionParameter(name) => {
                 write!(f, "Duplicate function parameter '{}'", name)?;
             }
+            OptimizerError::DuplicateTypeParameter(name) => {
+                write!(f, "Duplicate type parameter '{}'", name)?;
+            }
+            OptimizerError::TypeParameterNotInferred(name) => {
+                write!(f, "Cannot infer type parameter '{}'", name)?;
+            }
+            OptimizerError::InvalidInstantiation(name, types, err) => {
+                write!(f, "Cannot instantiate '{}' with {:?}: {}", name, types, err)?;
+            }
+            OptimizerError::MethodNotFound(data_type, name) => {
+                write!(f, "Method '{}' not found in '{:?}'", name, data_type)?;
+            }
+            OptimizerError::ReturnInDefer => {
+                write!(f, "Return statements are not allowed in a deferred statement")?;
+            }
+            OptimizerError::DeferNotAllowed => {
+                write!(f, "Defer statements are only allowed inside functions")?;
+            }
+            OptimizerError::NotAFunctionValue(name) => {
+                write!(f, "Function '{}' is generic or variadic and cannot be used as a value", name)?;
+            }
+            OptimizerError::InvalidReceiver(name, data_type) => {
+                write!(f, "Method '{}' must take 'self' of type '{:?}' or a reference to it as first parameter", name, data_type)?;
+            }
             OptimizerError::MissingMainFunction => {
                 write!(f, "Missing main function")?;
             }
             OptimizerError::MainFunctionNotCorrectlyDefined => {
                 write!(f, "Main function not correctly defined, should be 'fn main(): i32'")?;
             }
+            OptimizerError::OutsideOfLoop(statement) => {
+                write!(f, "'{}' is only allowed inside a loop", statement)?;
+            }
+            OptimizerError::TypeNotFound(name) => {
+                write!(f, "Type '{}' not found", name)?;
+            }
+            OptimizerError::TypeAlreadyExists(name) => {
+                write!(f, "Type '{}' already exists", name)?;
+            }
+            OptimizerError::TypeDefinitionNotAllowed => {
+                write!(f, "Type definition is not allowed here")?;
+            }
+            OptimizerError::RecursiveType(name) => {
+                write!(f, "Type '{}' contains itself, use a reference instead", name)?;
+            }
+            OptimizerError::RecursiveAlias(name) => {
+                write!(f, "Type alias '{}' refers to itself", name)?;
+            }
+            OptimizerError::DuplicateField(name) => {
+                write!(f, "Duplicate field '{}'", name)?;
+            }
+            OptimizerError::FieldNotFound(data_type, name) => {
+                write!(f, "Field '{}' not found in '{:?}'", name, data_type)?;
+            }
+            OptimizerError::MissingField(name) => {
+                write!(f, "Missing field '{}'", name)?;
+            }
+            OptimizerError::NotAssignable => {
+                write!(f, "Expression cannot be assigned to")?;
+            }
+            OptimizerError::ConstReference => {
+                write!(f, "Cannot assign through a constant reference")?;
+            }
+            OptimizerError::ComptimeReference => {
+                write!(f, "Cannot take a reference to a comptime value")?;
+            }
+            OptimizerError::DuplicateVariant(name) => {
+                write!(f, "Duplicate variant '{}'", name)?;
+            }
+            OptimizerError::VariantOverflow(name, value, data_type) => {
+                write!(f, "Value {} of variant '{}' does not fit in '{:?}'", value, name, data_type)?;
+            }
+            OptimizerError::ComptimeValueRequired => {
+                write!(f, "Expected a comptime value")?;
+            }
+            OptimizerError::ComptimeUnsupported(what) => {
+                write!(f, "Cannot evaluate {} at compile time", what)?;
+            }
+            OptimizerError::ComptimeStepLimit(limit) => {
+                write!(f, "Comptime evaluation exceeded {} steps", limit)?;
+            }
+            OptimizerError::ComptimeRecursionLimit(limit) => {
+                write!(f, "Comptime evaluation exceeded a call depth of {}", limit)?;
+            }
+            OptimizerError::IntegerOutOfRange(value, data_type, min, max) => {
+                write!(f, "Value {} does not fit in '{:?}', expected a value between {} and {}", value, data_type, min, max)?;
+            }
+            OptimizerError::FloatOutOfRange(value, data_type) => {
+                write!(f, "Value {} does not fit in '{:?}'", value, data_type)?;
+            }
+            OptimizerError::DivisionByZero => {
+                write!(f, "Division by zero")?;
+            }
+            OptimizerError::ShiftOverflow(amount) => {
+                write!(f, "Cannot shift by {}, the amount must be between 0 and {}", amount, MAX_COMPTIME_SHIFT)?;
+            }
+            OptimizerError::InvalidMatch(data_type) => {
+                write!(f, "Cannot match on '{:?}', expected a union", data_type)?;
+            }
+            OptimizerError::InvalidSwitch(data_type) => {
+                write!(f, "Cannot switch on '{:?}', expected an integer, a char or an enum", data_type)?;
+            }
+            OptimizerError::InvalidCaseRange(data_type) => {
+                write!(f, "Cannot use a range to switch on '{:?}'", data_type)?;
+            }
+            OptimizerError::OverlappingCase(case) => {
+                write!(f, "Case {} overlaps with a previous case", case)?;
+            }
+            OptimizerError::IndexOutOfBounds(index, length) => {
+                write!(f, "Index {} is out of bounds for length {}", index, length)?;
+            }
+            OptimizerError::InvalidRange(start, end) => {
+                write!(f, "Invalid range {}..{}, the start is after the end", start, end)?;
+            }
+            OptimizerError::NotIndexable(data_type) => {
+                write!(f, "Cannot index into '{:?}'", data_type)?;
+            }
+            OptimizerError::ArrayCopy => {
+                write!(f, "Arrays cannot be copied, use an array literal or a reference instead")?;
+            }
+            OptimizerError::NonExhaustiveMatch(missing) => {
+                write!(f, "Match is not exhaustive, missing ")?;
+                let mut first = true;
+                for variant in missing.iter() {
+                    if !first {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "'{}'", variant)?;
+                    first = false;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum OptimizerWarning {
+    Shadowing(String),
+}
+
+impl Display for OptimizerWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OptimizerWarning::Shadowing(variable) => {
+                write!(f, "Variable '{}' shadows a variable of an outer scope", variable)?;
+            }
         }
         Ok(())
     }
@@ -90,6 +283,8 @@ pub struct VariableData {
     var_type: Positioned<VarType>,
     data_type: Positioned<DataType>,
     initialized: bool,
+    c_name: String,
+    comptime_value: Option<ValueNode>,
 }
 
 #[derive(Clone, Debug)]
@@ -98,6 +293,65 @@ pub struct FunctionData {
     return_type: Positioned<DataType>,
     params: Vec<(Positioned<String>, Positioned<DataType>)>,
     list: bool,
+    generics: Vec<Positioned<String>>,
+    body: Vec<Positioned<Node>>,
+    receiver: Option<String>,
+}
+
+impl FunctionData {
+
+    pub fn c_name(&self) -> String {
+        // Methods become free functions prefixed by their type
+        return if let Some(receiver) = &self.receiver {
+            format!("{}__{}", receiver, self.name.data)
+        } else {
+            self.name.data.clone()
+        }
+    }
+
+}
+
+#[derive(Clone, Debug)]
+pub enum TypeData {
+    Struct {
+        name: Positioned<String>,
+        fields: Vec<(Positioned<String>, Positioned<DataType>)>,
+    },
+    Enum {
+        name: Positioned<String>,
+        backing_type: Positioned<DataType>,
+        variants: Vec<(Positioned<String>, i128)>,
+    },
+    Union {
+        name: Positioned<String>,
+        variants: Vec<(Positioned<String>, Vec<Positioned<DataType>>)>,
+    },
+    Alias {
+        name: Positioned<String>,
+        data_type: Positioned<DataType>,
+    },
+}
+
+impl TypeData {
+
+    pub fn name(&self) -> &Positioned<String> {
+        return match self {
+            TypeData::Struct { name, .. } => name,
+            TypeData::Enum { name, .. } => name,
+            TypeData::Union { name, .. } => name,
+            TypeData::Alias { name, .. } => name,
+        }
+    }
+
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BlockKind {
+    Plain,
+    Branch,
+    Loop,
+    Defer,
+    Comptime,
 }
 
 #[derive(Clone, Debug)]
@@ -105,6 +359,7 @@ pub enum Scope {
     Root {
         functions: Vec<FunctionData>,
         variables: Vec<VariableData>,
+        types: Vec<TypeData>,
     },
     Function {
         parent: Box<Scope>,
@@ -113,6 +368,11 @@ pub enum Scope {
         parameters: Vec<VariableData>,
         variables: Vec<VariableData>,
     },
+    Block {
+        parent: Box<Scope>,
+        kind: BlockKind,
+        variables: Vec<VariableData>,
+    },
 }
 
 impl Scope {
@@ -121,6 +381,53 @@ impl Scope {
         return match self {
             Scope::Root { .. } => self.clone(),
             Scope::Function { parent, .. } => *parent.clone(),
+            Scope::Block { parent, .. } => *parent.clone(),
+        }
+    }
+
+    pub fn root(&mut self) -> &mut Scope {
+        return match self {
+            Scope::Root { .. } => self,
+            Scope::Function { parent, .. } => parent.root(),
+            Scope::Block { parent, .. } => parent.root(),
+        }
+    }
+
+    pub fn return_type(&self) -> Option<Positioned<DataType>> {
+        return match self {
+            Scope::Root { .. } => None,
+            Scope::Function { return_type, .. } => Some(return_type.clone()),
+            Scope::Block { parent, .. } => parent.return_type(),
+        }
+    }
+
+    pub fn in_loop(&self) -> bool {
+        return match self {
+            Scope::Root { .. } => false,
+            Scope::Function { .. } => false,
+            Scope::Block { kind: BlockKind::Loop, .. } => true,
+            // A deferred statement or comptime block cannot jump out of its own scope
+            Scope::Block { kind: BlockKind::Defer | BlockKind::Comptime, .. } => false,
+            Scope::Block { parent, .. } => parent.in_loop(),
+        }
+    }
+
+    pub fn in_defer(&self) -> bool {
+        return match self {
+            Scope::Root { .. } => false,
+            Scope::Function { .. } => false,
+            Scope::Block { kind: BlockKind::Defer, .. } => true,
+            Scope::Block { kind: BlockKind::Comptime, .. } => false,
+            Scope::Block { parent, .. } => parent.in_defer(),
+        }
+    }
+
+    pub fn in_comptime(&self) -> bool {
+        return match self {
+            Scope::Root { .. } => false,
+            Scope::Function { .. } => false,
+            Scope::Block { kind: BlockKind::Comptime, .. } => true,
+            Scope::Block { parent, .. } => parent.in_comptime(),
         }
     }
 
@@ -129,16 +436,25 @@ impl Scope {
             Scope::Root { variables, .. } => {
                 for variable in variables.iter() {
                     if variable.name.data == variable_data.data.name.data {
-                        return Err(variable_data.convert(OptimizerError::Shadowing(variable.name.data.clone())));
+                        return Err(variable_data.convert(OptimizerError::VariableAlreadyExists(variable.name.data.clone())));
+                    }
+                }
+                variables.push(variable_data.data);
+                Ok(())
+            }
+            Scope::Function { parameters, variables, .. } => {
+                for variable in parameters.iter().chain(variables.iter()) {
+                    if variable.name.data == variable_data.data.name.data {
+                        return Err(variable_data.convert(OptimizerError::VariableAlreadyExists(variable.name.data.clone())));
                     }
                 }
                 variables.push(variable_data.data);
                 Ok(())
             }
-            Scope::Function { variables, .. } => {
+            Scope::Block { variables, .. } => {
                 for variable in variables.iter() {
                     if variable.name.data == variable_data.data.name.data {
-                        return Err(variable_data.convert(OptimizerError::Shadowing(variable.name.data.clone())));
+                        return Err(variable_data.convert(OptimizerError::VariableAlreadyExists(variable.name.data.clone())));
                     }
                 }
                 variables.push(variable_data.data);
@@ -151,14 +467,46 @@ impl Scope {
         return match self {
             Scope::Root { functions, .. } => {
                 for function in functions.iter() {
-                    if function.name.data == function_data.data.name.data {
+                    if function.name.data == function_data.data.name.data && function.receiver == function_data.data.receiver {
                         return Err(function_data.convert(OptimizerError::FunctionAlreadyExists(function.name.data.clone())));
                     }
                 }
                 functions.push(function_data.data);
                 Ok(())
             }
-            Scope::Function { .. } => Err(function_data.convert(OptimizerError::FunctionDefinitionNotAllowed)),
+            Scope::Function { .. } |
+            Scope::Block { .. } => Err(function_data.convert(OptimizerError::FunctionDefinitionNotAllowed)),
+        }
+    }
+
+    pub fn add_type(&mut self, type_data: Positioned<TypeData>) -> Result<(), Positioned<OptimizerError>> {
+        return match self {
+            Scope::Root { types, .. } => {
+                for custom_type in types.iter() {
+                    if custom_type.name().data == type_data.data.name().data {
+                        return Err(type_data.convert(OptimizerError::TypeAlreadyExists(custom_type.name().data.clone())));
+                    }
+                }
+                types.push(type_data.data);
+                Ok(())
+            }
+            Scope::Function { .. } |
+            Scope::Block { .. } => Err(type_data.convert(OptimizerError::TypeDefinitionNotAllowed)),
+        }
+    }
+
+    pub fn get_type(&mut self, name: String) -> Option<&mut TypeData> {
+        return match self {
+            Scope::Root { types, .. } => {
+                for custom_type in types.iter_mut() {
+                    if custom_type.name().data == name {
+                        return Some(custom_type);
+                    }
+                }
+                None
+            },
+            Scope::Function { parent, .. } |
+            Scope::Block { parent, .. } => parent.get_type(name),
         }
     }
 
@@ -185,20 +533,48 @@ impl Scope {
                 }
                 parent.get_variable(name)
             }
+            Scope::Block { variables, parent, .. } => {
+                for variable in variables.iter_mut() {
+                    if variable.name.data == name {
+                        return Some(variable);
+                    }
+                }
+                parent.get_variable(name)
+            }
         }
     }
 
-    pub fn get_function(&mut self, name: String) -> Option<&mut FunctionData> {
+    pub fn get_function(&mut self, receiver: Option<String>, name: String) -> Option<&mut FunctionData> {
         return match self {
             Scope::Root { functions, .. } => {
                 for function in functions.iter_mut() {
-                    if function.name.data == name {
+                    if function.name.data == name && function.receiver == receiver {
                         return Some(function);
                     }
                 }
                 None
             },
-            Scope::Function { parent, .. } => parent.get_function(name),
+            Scope::Function { parent, .. } |
+            Scope::Block { parent, .. } => parent.get_function(receiver, name),
+        }
+    }
+
+    pub fn get_declared_variable(&mut self, c_name: String) -> Option<&mut VariableData> {
+        // Lookup by the name used in the output, which differs for shadowing variables
+        return match self {
+            Scope::Root { variables, .. } => variables.iter_mut().find(|variable| variable.c_name == c_name),
+            Scope::Function { parameters, variables, parent, .. } => {
+                if let Some(variable) = parameters.iter_mut().chain(variables.iter_mut()).find(|variable| variable.c_name == c_name) {
+                    return Some(variable);
+                }
+                parent.get_declared_variable(c_name)
+            }
+            Scope::Block { variables, parent, .. } => {
+                if let Some(variable) = variables.iter_mut().find(|variable| variable.c_name == c_name) {
+                    return Some(variable);
+                }
+                parent.get_declared_variable(c_name)
+            }
         }
     }
 
@@ -210,6 +586,17 @@ pub struct Optimizer {
     index: usize,
     nodes: Vec<Positioned<Node>>,
     scope: Scope,
+    warn_shadowing: bool,
+    warnings: Vec<Positioned<OptimizerWarning>>,
+    shadow_count: usize,
+    type_bindings: Vec<(String, DataType)>,
+    // Aliases being resolved, to detect cycles
+    resolving_aliases: Vec<String>,
+    // Enums whose variant values are known, others are evaluated when first needed
+    evaluated_enums: Vec<String>,
+    receiver: Option<String>,
+    comptime_functions: Vec<(String, ComptimeFunction)>,
+    comptime_types: Vec<Option<DataType>>,
 }
 
 impl Optimizer {
@@ -222,11 +609,29 @@ impl Optimizer {
             nodes: vec![],
             scope: Scope::Root {
                 functions: vec![],
-                variables: vec![]
+                variables: vec![],
+                types: vec![]
             },
+            warn_shadowing: false,
+            warnings: vec![],
+            shadow_count: 0,
+            type_bindings: vec![],
+            resolving_aliases: vec![],
+            evaluated_enums: vec![],
+            receiver: None,
+            comptime_functions: vec![],
+            comptime_types: vec![],
         }
     }
 
+    pub fn warn_shadowing(&mut self, enabled: bool) {
+        self.warn_shadowing = enabled;
+    }
+
+    pub fn warnings(&self) -> &Vec<Positioned<OptimizerWarning>> {
+        return &self.warnings;
+    }
+
     pub fn take(self) -> String {
         return self.src;
     }
@@ -239,6 +644,176 @@ impl Optimizer {
         return self.ast.get(self.index).cloned();
     }
 
+    fn check_reserved_name(name: &Positioned<String>) -> Result<(), Positioned<OptimizerError>> {
+        // `__` joins the parts of generated C names: shadowing variables, temporaries, instances, methods and variants
+        return if name.data.contains("__") {
+            Err(name.convert(OptimizerError::ReservedName(name.data.clone())))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn declared_type(&mut self, written: &Positioned<DataType>, resolved: Positioned<DataType>) -> Positioned<DataType> {
+        // Declarations keep the name of an alias, for the generated C
+        return match (&written.data, resolved.data.clone()) {
+            (DataType::Custom(name), _) if !self.type_bindings.iter().any(|(generic, _)| generic == name) => {
+                if let Some(TypeData::Alias { .. }) = self.scope.get_type(name.clone()) {
+                    resolved.convert(DataType::Alias(name.clone(), Box::new(resolved.clone())))
+                } else {
+                    resolved
+                }
+            }
+            (DataType::Ref(written), DataType::Ref(inner)) => resolved.convert(DataType::Ref(Box::new(self.declared_type(written, *inner)))),
+            (DataType::ConstRef(written), DataType::ConstRef(inner)) => resolved.convert(DataType::ConstRef(Box::new(self.declared_type(written, *inner)))),
+            _ => resolved,
+        }
+    }
+
+    fn declare_variable(&mut self, mut variable: Positioned<VariableData>) -> Result<String, Positioned<OptimizerError>> {
+        Self::check_reserved_name(&variable.data.name)?;
+        // A shadowing variable gets its own name in C, so that its initializer still sees the outer one
+        let shadowing = self.scope.get_variable(variable.data.name.data.clone()).is_some();
+        if shadowing {
+            self.shadow_count += 1;
+            variable.data.c_name = format!("{}__{}", variable.data.name.data, self.shadow_count);
+        }
+        let c_name = variable.data.c_name.clone();
+        let name = variable.data.name.clone();
+        self.scope.add_variable(variable)?;
+
+        if shadowing && self.warn_shadowing {
+            self.warnings.push(name.convert(OptimizerWarning::Shadowing(name.data.clone())));
+        }
+        return Ok(c_name);
+    }
+
+    fn resolve_type(&mut self, data_type: Positioned<DataType>) -> Result<Positioned<DataType>, Positioned<OptimizerError>> {
+        return match data_type.data.clone() {
+            DataType::Custom(name) => {
+                // Type parameters of the function being instantiated
+                if let Some((_, bound)) = self.type_bindings.iter().find(|(generic, _)| *generic == name) {
+                    return Ok(data_type.convert(bound.clone()));
+                }
+                // Aliases are replaced by the type they name
+                if let Some(TypeData::Alias { data_type: target, .. }) = self.scope.get_type(name.clone()).cloned() {
+                    if self.resolving_aliases.contains(&name) {
+                        return Err(data_type.convert(OptimizerError::RecursiveAlias(name)));
+                    }
+                    self.resolving_aliases.push(name);
+                    let resolved = self.resolve_type(target);
+                    self.resolving_aliases.pop();
+                    return Ok(data_type.convert(resolved?.data));
+                }
+                match self.scope.get_type(name.clone()) {
+                    Some(TypeData::Struct { .. }) => Ok(data_type.convert(DataType::Struct(name))),
+                    Some(TypeData::Enum { .. }) => Ok(data_type.convert(DataType::Enum(name))),
+                    Some(TypeData::Union { .. }) => Ok(data_type.convert(DataType::Union(name))),
+                    Some(TypeData::Alias { .. }) => panic!("Should not happen!"),
+                    None => Err(data_type.convert(OptimizerError::TypeNotFound(name))),
+                }
+            }
+            DataType::Ref(inner) => Ok(data_type.convert(DataType::Ref(Box::new(self.resolve_type(*inner)?)))),
+            DataType::ConstRef(inner) => Ok(data_type.convert(DataType::ConstRef(Box::new(self.resolve_type(*inner)?)))),
+            DataType::Array(inner, size) => Ok(data_type.convert(DataType::Array(Box::new(self.resolve_type(*inner)?), size))),
+            DataType::Slice(inner) => Ok(data_type.convert(DataType::Slice(Box::new(self.resolve_type(*inner)?)))),
+            DataType::Function(params, return_type) => {
+                let mut r_params = vec![];
+                for param in params {
+                    r_params.push(self.resolve_type(param)?);
+                }
+                Ok(data_type.convert(DataType::Function(r_params, Box::new(self.resolve_type(*return_type)?))))
+            }
+            _ => Ok(data_type),
+        }
+    }
+
+    fn resolve_type_name(&mut self, name: Positioned<String>) -> Result<Positioned<String>, Positioned<OptimizerError>> {
+        // Literals and variants written with an alias use the name of the aliased type
+        if let Some(TypeData::Alias { .. }) = self.scope.get_type(name.data.clone()) {
+            return match self.resolve_type(name.convert(DataType::Custom(name.data.clone())))?.data {
+                DataType::Struct(target) | DataType::Enum(target) | DataType::Union(target) => Ok(name.convert(target)),
+                _ => Ok(name),
+            }
+        }
+        return Ok(name);
+    }
+
+    fn coerce(&mut self, value: Positioned<Node>, from: &DataType, to: &DataType) -> Result<Positioned<Node>, Positioned<OptimizerError>> {
+        Self::check_integer_range(&value, from, to)?;
+        let value = Self::float_literal(value, from, to)?;
+
+        // String literals are wrapped into a str
+        if let (DataType::ComptimeString, DataType::String) = (from, to) {
+            return Ok(value.convert(Node::Casting(Box::new(value.clone()), value.convert(DataType::String))));
+        }
+
+        // Arrays are implicitly viewed as slices
+        if let DataType::Slice(inner) = to {
+            match from {
+                DataType::Array(_, size) => {
+                    let base = if let Node::ArrayLiteral(_, elements) = value.data.clone() {
+                        value.convert(Node::ArrayLiteral(Some(*inner.clone()), elements))
+                    } else {
+                        self.check_assignable(&value)?;
+                        value.clone()
+                    };
+                    let base_type = value.convert(DataType::Array(inner.clone(), *size));
+                    return Ok(value.convert(Node::Slice(Box::new(base), Some(base_type), None, None)));
+                }
+                DataType::Ref(ref_inner) => {
+                    if let DataType::Array(_, size) = ref_inner.data {
+                        let base = value.convert(Node::UnaryOperation(value.convert(Operator::Deref), Box::new(value.clone())));
+                        let base_type = value.convert(DataType::Array(inner.clone(), size));
+                        return Ok(value.convert(Node::Slice(Box::new(base), Some(base_type), None, None)));
+                    }
+                }
+                _ => {}
+            }
+        }
+        return Ok(value);
+    }
+
+    fn check_integer_range(value: &Positioned<Node>, from: &DataType, to: &DataType) -> Result<(), Positioned<OptimizerError>> {
+        // Comptime integers must fit in the concrete type
+        match (from, to, &value.data) {
+            (DataType::ComptimeNumber, _, Node::Value(ValueNode::Number(num))) => {
+                if let (Some((min, max)), Some(number)) = (to.literal_range(), BigInt::parse(num)) {
+                    if number < BigInt::from_i128(min) || number > BigInt::from_i128(max) {
+                        return Err(value.convert(OptimizerError::IntegerOutOfRange(num.clone(), to.clone(), min, max)));
+                    }
+                }
+            }
+            (DataType::Array(from_inner, _), DataType::Array(to_inner, _), Node::ArrayLiteral(_, elements)) => {
+                for element in elements.iter() {
+                    Self::check_integer_range(element, &from_inner.data, &to_inner.data)?;
+                }
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    fn float_literal(value: Positioned<Node>, from: &DataType, to: &DataType) -> Result<Positioned<Node>, Positioned<OptimizerError>> {
+        // An integer literal used as a float is written as a float literal, C would read it as an integer
+        if let (DataType::ComptimeNumber, DataType::F32 | DataType::F64, Node::Value(ValueNode::Number(num))) = (from, to, &value.data) {
+            let max = if *to == DataType::F32 { f32::MAX as f64 } else { f64::MAX };
+            if num.parse::<f64>().map(|number| number.abs() > max).unwrap_or(true) {
+                return Err(value.convert(OptimizerError::FloatOutOfRange(num.clone(), to.clone())));
+            }
+            return Ok(value.convert(Node::Value(ValueNode::Float(format!("{}.0", num)))));
+        }
+        return Ok(value);
+    }
+
+    fn check_array_copy(&self, data_type: &DataType, value: &Positioned<Node>) -> Result<(), Positioned<OptimizerError>> {
+        // C can only initialize an array from a literal
+        return match (data_type, &value.data) {
+            (DataType::Array(_, _), Node::ArrayLiteral(_, _)) => Ok(()),
+            (DataType::Array(_, _), _) => Err(value.convert(OptimizerError::ArrayCopy)),
+            _ => Ok(()),
+        }
+    }
+
     fn check_bin_op(&mut self, left: Positioned<Node>, operator: Positioned<Operator>, right: Positioned<Node>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
         let start = left.start.clone();
         let end = right.end.clone();
@@ -247,9 +822,37 @@ impl Optimizer {
         let right_result = self.optimize_node(right)?;
 
         return if let Some(output_type) = operator.data.check_compatibility(left_result.0.clone().unwrap(), right_result.0.clone().unwrap()) {
+            let (left_node, right_node) = (left_result.1.unwrap(), right_result.1.unwrap());
+            // A literal mixed with a concrete integer takes its type, except for a shift amount
+            Self::check_integer_range(&left_node, left_result.0.as_ref().unwrap(), &output_type)?;
+            if !matches!(operator.data, Operator::LeftShift | Operator::RightShift) {
+                Self::check_integer_range(&right_node, right_result.0.as_ref().unwrap(), &output_type)?;
+            }
+            // Comparisons produce a bool, the literal is compared as the type of the other operand
+            let (left_node, right_node) = if matches!(operator.data, Operator::Greater | Operator::GreaterOrEqual | Operator::Less | Operator::LessOrEqual | Operator::Equal | Operator::NotEqual) {
+                Self::check_integer_range(&left_node, left_result.0.as_ref().unwrap(), right_result.0.as_ref().unwrap())?;
+                Self::check_integer_range(&right_node, right_result.0.as_ref().unwrap(), left_result.0.as_ref().unwrap())?;
+                (Self::float_literal(left_node, left_result.0.as_ref().unwrap(), right_result.0.as_ref().unwrap())?,
+                 Self::float_literal(right_node, right_result.0.as_ref().unwrap(), left_result.0.as_ref().unwrap())?)
+            } else {
+                (Self::float_literal(left_node, left_result.0.as_ref().unwrap(), &output_type)?,
+                 Self::float_literal(right_node, right_result.0.as_ref().unwrap(), &output_type)?)
+            };
+            if let (Node::Value(left_value), Node::Value(right_value)) = (&left_node.data, &right_node.data) {
+                let folded = Self::fold_bin_op(left_value, &operator.data, right_value).map_err(|err| Positioned::new(err, start.clone(), end.clone()))?;
+                if let Some(value) = folded {
+                    return Ok((Some(output_type), Some(Positioned::new(Node::Value(value), start, end))));
+                }
+            }
+            // C would compare the addresses of the strings, only comptime code can compare their contents
+            if let (Some(DataType::ComptimeString), Some(DataType::ComptimeString)) = (&left_result.0, &right_result.0) {
+                if output_type == DataType::ComptimeBool && self.comptime_types.is_empty() {
+                    return Err(Positioned::new(OptimizerError::ComptimeValueRequired, start, end));
+                }
+            }
             Ok((
                 Some(output_type),
-                Some(Positioned::new(Node::BinaryOperation(Box::new(left_result.1.unwrap()), operator.clone(), Box::new(right_result.1.unwrap())), start, end))
+                Some(Positioned::new(Node::BinaryOperation(Box::new(left_node), operator.clone(), Box::new(right_node)), start, end))
             ))
         } else {
             Err(Positioned::new(OptimizerError::IncompatibleBinOperator(left_result.0.unwrap(), operator.data, right_result.0.unwrap()), start, end))
@@ -262,26 +865,212 @@ impl Optimizer {
 
         let value_result = self.optimize_node(value)?;
         return if let Some(output_type) = operator.data.is_unary_compatible(value_result.0.clone().unwrap()) {
-            Ok((Some(output_type), Some(Positioned::new(Node::UnaryOperation(operator, Box::new(value_result.1.unwrap())), start, end))))
+            let value_node = value_result.1.unwrap();
+            if let (Operator::Ref | Operator::ConstRef, Node::Value(_)) = (&operator.data, &value_node.data) {
+                return Err(Positioned::new(OptimizerError::ComptimeReference, start, end));
+            }
+            if let Node::Value(value) = &value_node.data {
+                let folded = Self::fold_unary_op(&operator.data, value).map_err(|err| Positioned::new(err, start.clone(), end.clone()))?;
+                if let Some(value) = folded {
+                    return Ok((Some(output_type), Some(Positioned::new(Node::Value(value), start, end))));
+                }
+            }
+            Ok((Some(output_type), Some(Positioned::new(Node::UnaryOperation(operator, Box::new(value_node)), start, end))))
         } else {
             Err(Positioned::new(OptimizerError::IncompatibleUnaryOperator(operator.data, value_result.0.unwrap()), start, end))
         }
     }
 
+    pub fn fold_bin_op(left: &ValueNode, operator: &Operator, right: &ValueNode) -> Result<Option<ValueNode>, OptimizerError> {
+        return match (left, right) {
+            (ValueNode::Number(left), ValueNode::Number(right)) => {
+                let (left, right) = match (BigInt::parse(left), BigInt::parse(right)) {
+                    (Some(left), Some(right)) => (left, right),
+                    _ => return Ok(None),
+                };
+                let number = |num: BigInt| Ok(Some(ValueNode::Number(num.to_string())));
+                // Comptime integers never overflow, the range is checked once coerced into a concrete type
+                match operator {
+                    Operator::Plus => number(&left + &right),
+                    Operator::Minus => number(&left - &right),
+                    Operator::Multiply => number(&left * &right),
+                    Operator::Divide => left.checked_div(&right).map(number).unwrap_or(Err(OptimizerError::DivisionByZero)),
+                    Operator::Remainder => left.checked_rem(&right).map(number).unwrap_or(Err(OptimizerError::DivisionByZero)),
+                    Operator::LeftShift | Operator::RightShift => {
+                        let amount = match right.to_i128() {
+                            Some(amount) if (0..=MAX_COMPTIME_SHIFT as i128).contains(&amount) => amount as usize,
+                            _ => return Err(OptimizerError::ShiftOverflow(right.to_string())),
+                        };
+                        if let Operator::LeftShift = operator {
+                            number(&left << amount)
+                        } else {
+                            number(&left >> amount)
+                        }
+                    }
+                    Operator::BitAnd => number(&left & &right),
+                    Operator::BitOr => number(&left | &right),
+                    Operator::BitXor => number(&left ^ &right),
+                    _ => Ok(Self::fold_comparison(operator, left.cmp(&right))),
+                }
+            }
+            (ValueNode::Char(left), ValueNode::Char(right)) => {
+                let (left, right) = match (Self::decode_char(left), Self::decode_char(right)) {
+                    (Some(left), Some(right)) => (left, right),
+                    _ => return Ok(None),
+                };
+                match operator {
+                    Operator::Plus => Ok(left.checked_add(right).and_then(|chr| Self::encode_char(chr, '\'')).map(ValueNode::Char)),
+                    _ => Ok(Self::fold_comparison(operator, left.cmp(&right))),
+                }
+            }
+            (ValueNode::Char(chr), ValueNode::Number(num)) => {
+                let (chr, num) = match (Self::decode_char(chr), num.parse::<i128>()) {
+                    (Some(chr), Ok(num)) => (chr as i128, num),
+                    _ => return Ok(None),
+                };
+                let result = match operator {
+                    Operator::Plus => chr.checked_add(num),
+                    Operator::Minus => chr.checked_sub(num),
+                    _ => return Ok(None),
+                };
+                Ok(result.and_then(|chr| u8::try_from(chr).ok()).and_then(|chr| Self::encode_char(chr, '\'')).map(ValueNode::Char))
+            }
+            (ValueNode::String(left), ValueNode::String(right)) => {
+                match operator {
+                    Operator::Plus => Ok(Some(ValueNode::String(format!("{}{}", left, right)))),
+                    _ => match (Self::unescape(left), Self::unescape(right)) {
+                        (Some(left), Some(right)) => Ok(Self::fold_comparison(operator, left.cmp(&right))),
+                        _ => Ok(None),
+                    }
+                }
+            }
+            (ValueNode::Char(chr), ValueNode::String(str)) if matches!(operator, Operator::Plus) => {
+                Ok(Self::decode_char(chr).and_then(|chr| Self::encode_char(chr, '"')).map(|chr| ValueNode::String(format!("{}{}", chr, str))))
+            }
+            (ValueNode::String(str), ValueNode::Char(chr)) if matches!(operator, Operator::Plus) => {
+                Ok(Self::decode_char(chr).and_then(|chr| Self::encode_char(chr, '"')).map(|chr| ValueNode::String(format!("{}{}", str, chr))))
+            }
+            (ValueNode::Boolean(left), ValueNode::Boolean(right)) => {
+                match operator {
+                    Operator::And => Ok(Some(ValueNode::Boolean(*left && *right))),
+                    Operator::Or => Ok(Some(ValueNode::Boolean(*left || *right))),
+                    Operator::Xor => Ok(Some(ValueNode::Boolean(*left ^ *right))),
+                    _ => Ok(Self::fold_comparison(operator, left.cmp(right))),
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn fold_comparison(operator: &Operator, ordering: std::cmp::Ordering) -> Option<ValueNode> {
+        let result = match operator {
+            Operator::Equal => ordering.is_eq(),
+            Operator::NotEqual => ordering.is_ne(),
+            Operator::Less => ordering.is_lt(),
+            Operator::LessOrEqual => ordering.is_le(),
+            Operator::Greater => ordering.is_gt(),
+            Operator::GreaterOrEqual => ordering.is_ge(),
+            _ => return None,
+        };
+        return Some(ValueNode::Boolean(result));
+    }
+
+    pub fn fold_unary_op(operator: &Operator, value: &ValueNode) -> Result<Option<ValueNode>, OptimizerError> {
+        return match (operator, value) {
+            (Operator::Plus, ValueNode::Number(_)) => Ok(Some(value.clone())),
+            (Operator::Minus, ValueNode::Number(num)) => Ok(BigInt::parse(num).map(|num| ValueNode::Number((-&num).to_string()))),
+            (Operator::BitNot, ValueNode::Number(num)) => Ok(BigInt::parse(num).map(|num| ValueNode::Number((!&num).to_string()))),
+            (Operator::Not, ValueNode::Boolean(bool)) => Ok(Some(ValueNode::Boolean(!bool))),
+            _ => Ok(None),
+        }
+    }
+
+    fn unescape(str: &str) -> Option<Vec<u8>> {
+        let mut out = Vec::new();
+        let mut chars = str.chars();
+        while let Some(chr) = chars.next() {
+            let byte = if chr == '\\' {
+                match chars.next()? {
+                    '0' => 0,
+                    't' => b'\t',
+                    'n' => b'\n',
+                    'r' => b'\r',
+                    other => u8::try_from(other).ok()?,
+                }
+            } else {
+                u8::try_from(chr).ok()?
+            };
+            out.push(byte);
+        }
+        return Some(out);
+    }
+
+    pub fn decode_char(chr: &str) -> Option<u8> {
+        return match Self::unescape(chr)?.as_slice() {
+            [byte] => Some(*byte),
+            _ => None,
+        }
+    }
+
+    /// Encode a byte for a char or string literal delimited by `quote`, only printable ASCII and the
+    /// escapes known by the Lexer can be represented
+    pub fn encode_char(chr: u8, quote: char) -> Option<String> {
+        return match chr {
+            0 => Some("\\0".to_string()),
+            b'\t' => Some("\\t".to_string()),
+            b'\n' => Some("\\n".to_string()),
+            b'\r' => Some("\\r".to_string()),
+            b'\\' => Some("\\\\".to_string()),
+            _ if chr as char == quote => Some(format!("\\{}", quote)),
+            0x20..=0x7e => Some((chr as char).to_string()),
+            _ => None,
+        }
+    }
+
     fn check_variable_definition(&mut self, position: Positioned<()>, var_type: Positioned<VarType>, name: Positioned<String>, data_type: Option<Positioned<DataType>>, value: Option<Box<Positioned<Node>>>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        let written_type = data_type.clone();
+        let data_type = if let Some(data_type) = data_type {
+            Some(self.resolve_type(data_type)?)
+        } else {
+            None
+        };
         let f_data_type;
+        let mut f_value = None;
         let end;
         if let Some(value) = value.clone() {
-            let result_value = self.optimize_node(*value.clone())?;
+            let mut result_value = self.optimize_node(*value.clone())?;
+            if result_value.0 == Some(DataType::Void) {
+                return Err(value.convert(OptimizerError::VoidValue));
+            }
+
+            // Constants calling Lead functions are evaluated at compile time, unless they need a runtime value.
+            // Global constants have no runtime to fall back on, C requires a constant initializer
+            if var_type.data == VarType::Const && self.calls_comptime_function(&result_value.1.as_ref().unwrap().data) == Some(true) {
+                let global = matches!(self.scope, Scope::Root { .. });
+                let node = result_value.1.as_ref().unwrap();
+                match Interpreter::run(&self.comptime_functions, |interpreter| interpreter.evaluate(node)) {
+                    Ok(evaluated) => result_value.1 = Some(evaluated.into_node(&value.convert(()))),
+                    Err(Positioned { data: OptimizerError::ComptimeUnsupported(_), .. }) if !global => {}
+                    Err(err) => return Err(err),
+                }
+            }
+            if var_type.data == VarType::Const && matches!(self.scope, Scope::Root { .. }) && !self.is_comptime_value(&result_value.1.as_ref().unwrap().data) {
+                return Err(value.convert(OptimizerError::ComptimeValueRequired));
+            }
 
             if let Some(data_type) = data_type {
                 if !result_value.0.as_ref().unwrap().is_convertible(data_type.data.clone()) {
                     return Err(value.convert(OptimizerError::IncompatibleTypes(data_type.data, result_value.0.unwrap())))
                 }
                 f_data_type = data_type;
+            } else if var_type.data != VarType::Const && result_value.0 != Some(DataType::ComptimeString) {
+                // Variables get the concrete type of a literal, string literals stay C strings
+                f_data_type = result_value.1.clone().unwrap().convert(result_value.0.clone().unwrap().default_type());
             } else {
-                f_data_type = result_value.1.unwrap().convert(result_value.0.unwrap());
+                f_data_type = result_value.1.clone().unwrap().convert(result_value.0.clone().unwrap());
             }
+            self.check_array_copy(&f_data_type.data, result_value.1.as_ref().unwrap())?;
+            f_value = Some(Box::new(self.coerce(result_value.1.unwrap(), result_value.0.as_ref().unwrap(), &f_data_type.data)?));
             end = value.end.clone();
         } else if let Some(data_type) = data_type {
             f_data_type = data_type.clone();
@@ -290,25 +1079,50 @@ impl Optimizer {
             return Err(Positioned::new(OptimizerError::MissingType, var_type.start.clone(), name.end.clone()));
         }
 
+        // Comptime integer constants have no C type wide enough, they are inlined where used instead.
+        // String constants are inlined too, so they can still become a str with a known length
+        let comptime_value = match (&var_type.data, &f_data_type.data, f_value.as_ref().map(|value| &value.data)) {
+            (VarType::Const, DataType::ComptimeNumber | DataType::ComptimeString, Some(Node::Value(value))) => Some(value.clone()),
+            _ => None,
+        };
+
         // Initialize variable
         let variable = VariableData {
             name: name.clone(),
             var_type: var_type.clone(),
             data_type: f_data_type.clone(),
-            initialized: value.is_some()
+            initialized: value.is_some(),
+            c_name: name.data.clone(),
+            comptime_value: comptime_value.clone(),
         };
-        self.scope.add_variable(position.convert(variable));
+        let c_name = self.declare_variable(position.convert(variable))?;
+        if comptime_value.is_some() && f_data_type.data == DataType::ComptimeNumber {
+            return Ok((None, None));
+        }
 
         // Return node
+        let f_data_type = match written_type {
+            Some(written) => self.declared_type(&written, f_data_type),
+            None => f_data_type,
+        };
         Ok((
                None,
-               Some(Positioned::new(Node::VariableDefinition(var_type.clone(), name.clone(), Some(f_data_type.clone()), value.clone()), var_type.start.clone(), end))
+               Some(Positioned::new(Node::VariableDefinition(var_type.clone(), name.convert(c_name), Some(f_data_type.clone()), f_value), var_type.start.clone(), end))
         ))
     }
 
     fn optimize_casting(&mut self, left: Positioned<Node>, right: Positioned<DataType>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        let right = self.resolve_type(right)?;
         let left_result = self.optimize_node(left.clone())?;
 
+        if let (DataType::String, DataType::ConstRef(_)) = (left_result.0.clone().unwrap(), &right.data) {
+            if left_result.0.clone().unwrap().is_castable(right.data.clone()) {
+                // Strings are passed to C through their data pointer
+                let ptr = Node::FieldAccess(Box::new(left_result.1.unwrap()), right.convert("ptr".to_string()));
+                return Ok((Some(right.data.clone()), Some(Positioned::new(ptr, left.start.clone(), right.end.clone()))));
+            }
+        }
+
         return if left_result.0.clone().unwrap().is_castable(right.data.clone()) {
             Ok((
                 Some(right.data.clone()),
@@ -321,48 +1135,103 @@ impl Optimizer {
 
     fn optimize_variable_call(&mut self, id: Positioned<String>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
         return if let Some(variable) = self.scope.get_variable(id.data.clone()) {
-            Ok((Some(variable.data_type.data.clone()), Some(id.clone().convert(Node::VariableCall(id.data.clone())))))
+            if let Some(value) = variable.comptime_value.clone() {
+                return Ok((Some(variable.data_type.data.clone()), Some(id.convert(Node::Value(value)))));
+            }
+            Ok((Some(variable.data_type.data.clone()), Some(id.clone().convert(Node::VariableCall(variable.c_name.clone())))))
+        } else if let Some(function) = self.scope.get_function(None, id.data.clone()).cloned() {
+            // Functions are values of their pointer type
+            if function.generics.len() != 0 || function.list {
+                return Err(id.clone().convert(OptimizerError::NotAFunctionValue(id.data)));
+            }
+            let params = function.params.into_iter().map(|(_, p_type)| p_type).collect();
+            Ok((Some(DataType::Function(params, Box::new(function.return_type))), Some(id.clone().convert(Node::VariableCall(id.data)))))
         } else {
             Err(id.clone().convert(OptimizerError::VariableNotFound(id.data)))
         }
     }
 
-    fn optimize_variable_assignment(&mut self, id: Positioned<String>, value: Positioned<Node>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
-        let value_result = self.optimize_node(value.clone())?;
-        if let Some(variable) = self.scope.get_variable(id.data.clone()) {
-            match (variable.var_type.data.clone(), variable.initialized.clone()) {
-                (VarType::Var, _) |
-                (VarType::Let, false) => {
-                    if !value_result.0.clone().unwrap().is_convertible(variable.data_type.data.clone()) {
-                        if let DataType::Ref(inner) = variable.data_type.data.clone() {
-                            if !value_result.0.clone().unwrap().is_convertible(inner.data) {
-                                Err(Positioned::new(OptimizerError::IncompatibleTypes(value_result.0.unwrap(), variable.data_type.data.clone()), id.start.clone(), value.end.clone()))
-                            } else {
-                                Ok((None, Some(Positioned::new(Node::VariableAssignment(true, id.clone(), Box::new(value_result.1.unwrap())), id.start.clone(), value.end.clone()))))
-                            }
-                        } else {
-                            Err(Positioned::new(OptimizerError::IncompatibleTypes(value_result.0.unwrap(), variable.data_type.data.clone()), id.start.clone(), value.end.clone()))
-                        }
-                    } else {
-                        Ok((None, Some(Positioned::new(Node::VariableAssignment(false, id.clone(), Box::new(value_result.1.unwrap())), id.start.clone(), value.end.clone()))))
-                    }
-                }
-                _ => Err(Positioned::new(OptimizerError::VariableCannotBeModified(id.data.clone()), id.start.clone(), value.end.clone())),
+    fn compound_type(&self, position: Positioned<()>, operator: &Option<Positioned<Operator>>, target: DataType, value: DataType) -> Result<DataType, Positioned<OptimizerError>> {
+        // `a op= b` stores the result of `a op b`
+        return match operator {
+            Some(operator) => operator.data.check_compatibility(target.clone(), value.clone())
+                .ok_or(position.convert(OptimizerError::IncompatibleBinOperator(target, operator.data.clone(), value))),
+            None => Ok(value),
+        }
+    }
+
+    fn resolve_signature(&mut self, params: Vec<(Positioned<String>, Positioned<DataType>)>, return_type: Option<Positioned<DataType>>) -> Result<(Vec<(Positioned<String>, Positioned<DataType>)>, Option<Positioned<DataType>>), Positioned<OptimizerError>> {
+        let mut r_params = Vec::new();
+        for (p_name, p_type) in params {
+            let p_type = self.resolve_type(p_type)?;
+            if let DataType::Array(_, _) = p_type.data {
+                return Err(p_type.convert(OptimizerError::ArrayCopy));
             }
-        } else {
-            Err(Positioned::new(OptimizerError::VariableNotFound(id.data.clone()), id.start.clone(), value.end.clone()))
+            r_params.push((p_name, p_type));
         }
+        let r_return_type = if let Some(return_type) = return_type {
+            let return_type = self.resolve_type(return_type)?;
+            if let DataType::Array(_, _) = return_type.data {
+                return Err(return_type.convert(OptimizerError::ArrayCopy));
+            }
+            Some(return_type)
+        } else {
+            None
+        };
+        return Ok((r_params, r_return_type));
     }
 
-    fn optimize_function_definition(&mut self, position: Positioned<()>, name: Positioned<String>, params: Vec<(Positioned<String>, Positioned<DataType>)>, return_type: Option<Positioned<DataType>>, body: Vec<Positioned<Node>>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+    fn optimize_function_definition(&mut self, position: Positioned<()>, name: Positioned<String>, generics: Vec<Positioned<String>>, params: Vec<(Positioned<String>, Positioned<DataType>)>, return_type: Option<Positioned<DataType>>, body: Vec<Positioned<Node>>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        // Generic functions are only checked once instantiated by a call
+        if generics.len() != 0 {
+            for (i, generic) in generics.iter().enumerate() {
+                if generics[..i].iter().any(|other| other.data == generic.data) {
+                    return Err(generic.clone().convert(OptimizerError::DuplicateTypeParameter(generic.data.clone())));
+                }
+            }
+            let function_data = FunctionData {
+                name: name.clone(),
+                return_type: return_type.unwrap_or(name.clone().convert(DataType::Void)),
+                params,
+                list: false,
+                generics,
+                body,
+                receiver: self.receiver.clone(),
+            };
+            self.scope.add_function(position.convert(function_data))?;
+            return Ok((None, None));
+        }
+
+        let (written_params, written_return_type) = (params.clone(), return_type.clone());
+        let (params, return_type) = self.resolve_signature(params, return_type)?;
+
         // Save function symbol
         let function_data = FunctionData {
             name: name.clone(),
             return_type: return_type.clone().unwrap_or(name.clone().convert(DataType::Void)),
             params: params.clone(),
             list: false,
+            generics: vec![],
+            body: vec![],
+            receiver: self.receiver.clone(),
         };
-        self.scope.add_function(position.convert(function_data));
+        let c_name = name.convert(function_data.c_name());
+        self.scope.add_function(position.convert(function_data))?;
+
+        // Methods take their receiver as first parameter
+        if let Some(receiver) = self.receiver.clone() {
+            let receiver_type = self.resolve_type(name.convert(DataType::Custom(receiver)))?;
+            let valid = match params.first() {
+                Some((p_name, p_type)) if p_name.data == "self" => match &p_type.data {
+                    DataType::Ref(inner) | DataType::ConstRef(inner) => inner.data == receiver_type.data,
+                    data_type => *data_type == receiver_type.data,
+                },
+                _ => false,
+            };
+            if !valid {
+                return Err(name.convert(OptimizerError::InvalidReceiver(name.data.clone(), receiver_type.data)));
+            }
+        }
 
         // Check & convert params to variable data
         let mut f_params: Vec<VariableData> = vec![];
@@ -372,11 +1241,14 @@ impl Optimizer {
                     return Err(p_name.clone().convert(OptimizerError::DuplicateFunctionParameter(p_name.data.clone())));
                 }
             }
+            Self::check_reserved_name(p_name)?;
             f_params.push(VariableData {
                 name: p_name.clone(),
                 var_type: p_name.convert(VarType::FunctionParam),
                 data_type: p_type.clone(),
-                initialized: false
+                initialized: false,
+                c_name: p_name.data.clone(),
+                comptime_value: None,
             });
         }
 
@@ -391,20 +1263,43 @@ impl Optimizer {
 
         let mut new_body = Vec::new();
         for node in body.iter() {
-            new_body.push(self.optimize_node(node.clone())?.1.unwrap());
+            if let Some(node) = self.optimize_node(node.clone())?.1 {
+                new_body.push(node);
+            }
         }
 
         // Remove Scope
         self.scope = self.scope.parent();
 
-        return Ok((None, Some(position.convert(Node::FunctionDefinition(name, params, return_type, new_body)))));
+        // Keep the checked body, for the function to be evaluated at compile time
+        self.comptime_functions.push((c_name.data.clone(), ComptimeFunction {
+            params: params.clone(),
+            return_type: return_type.clone().unwrap_or(name.convert(DataType::Void)),
+            body: new_body.clone(),
+        }));
+
+        let params = params.into_iter().zip(written_params.iter())
+            .map(|((p_name, p_type), (_, written))| (p_name, self.declared_type(written, p_type)))
+            .collect();
+        let return_type = match (return_type, written_return_type) {
+            (Some(return_type), Some(written)) => Some(self.declared_type(&written, return_type)),
+            (return_type, _) => return_type,
+        };
+        return Ok((None, Some(position.convert(Node::FunctionDefinition(c_name, vec![], params, return_type, new_body)))));
     }
 
     fn optimize_return(&mut self, node: Positioned<Node>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
-        return if let Scope::Function { return_type, .. } = self.scope.clone() {
+        if self.scope.in_defer() {
+            return Err(node.convert(OptimizerError::ReturnInDefer));
+        }
+        if self.scope.in_comptime() {
+            return self.optimize_comptime_return(node);
+        }
+        return if let Some(return_type) = self.scope.return_type() {
             let node_result = self.optimize_node(node.clone())?;
             if node_result.clone().0.unwrap().is_convertible(return_type.data.clone()) {
-                Ok((None, Some(node.convert(Node::Return(Box::new(node_result.1.clone().unwrap()))))))
+                let r_value = self.coerce(node_result.1.unwrap(), node_result.0.as_ref().unwrap(), &return_type.data)?;
+                Ok((None, Some(node.convert(Node::Return(Box::new(r_value))))))
             } else {
                 Err(node_result.1.unwrap().convert(OptimizerError::IncompatibleTypes(return_type.data, node_result.0.unwrap())))
             }
@@ -413,47 +1308,1098 @@ impl Optimizer {
         }
     }
 
-    fn optimize_function_call(&mut self, position: Positioned<()>, name: Positioned<String>, params: Vec<Positioned<Node>>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
-        return if let Some(function) = self.scope.get_function(name.data.clone()).cloned() {
-            let mut f_params = VecDeque::new();
-            // Copy the params
-            for param in function.params.iter() {
-                f_params.push_back(param.clone());
-            }
-            // Check the given params
-            let mut r_params = Vec::new();
-            for v_param in params.iter() {
-                if let Some((p_name, p_type)) = f_params.pop_back() {
-                    let result = self.optimize_node(v_param.clone())?;
-                    if result.0.clone().unwrap().is_convertible(p_type.data.clone()) {
-                        r_params.push(result.1.unwrap());
+    fn optimize_comptime_return(&mut self, node: Positioned<Node>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        // Returns agree on the type of the comptime block, a concrete type wins over a comptime one
+        let node_result = self.optimize_node(node.clone())?;
+        let value_type = node_result.0.clone().unwrap();
+        let return_type = match self.comptime_types.last_mut().unwrap() {
+            Some(return_type) if return_type.is_comptime() && !value_type.is_convertible(return_type.clone()) && return_type.is_convertible(value_type.clone()) => {
+                *return_type = value_type.clone();
+                value_type.clone()
+            }
+            Some(return_type) => return_type.clone(),
+            return_type => {
+                *return_type = Some(value_type.clone());
+                value_type.clone()
+            }
+        };
+        return if value_type.is_convertible(return_type.clone()) {
+            let r_value = self.coerce(node_result.1.unwrap(), &value_type, &return_type)?;
+            Ok((None, Some(node.convert(Node::Return(Box::new(r_value))))))
+        } else {
+            Err(node_result.1.unwrap().convert(OptimizerError::IncompatibleTypes(return_type, value_type)))
+        }
+    }
+
+    fn optimize_comptime(&mut self, position: Positioned<()>, body: Vec<Positioned<Node>>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        self.comptime_types.push(None);
+        let body = self.optimize_block(BlockKind::Comptime, body);
+        let data_type = self.comptime_types.pop().unwrap().unwrap_or(DataType::Void);
+
+        let body = body?;
+        let value = Interpreter::run(&self.comptime_functions, |interpreter| interpreter.evaluate_block(&body))?;
+        let node = value.into_node(&position);
+        // Only the return taken is checked against the final type
+        Self::check_integer_range(&node, &DataType::ComptimeNumber, &data_type)?;
+        return Ok((Some(data_type), Some(node)));
+    }
+
+    /// Whether a constant initializer calls Lead functions on comptime values only, `None` if it
+    /// depends on a runtime value
+    fn is_comptime_value(&mut self, node: &Node) -> bool {
+        return match node {
+            Node::Value(_) | Node::EnumVariant(_, _) => true,
+            // Other global constants, checked by this same rule
+            Node::VariableCall(name) => self.scope.get_variable(name.clone()).map_or(false, |variable| variable.var_type.data == VarType::Const),
+            Node::UnaryOperation(_, value) | Node::Casting(value, _) => self.is_comptime_value(&value.data),
+            Node::BinaryOperation(left, _, right) => self.is_comptime_value(&left.data) && self.is_comptime_value(&right.data),
+            Node::ArrayLiteral(_, elements) | Node::UnionLiteral(_, _, elements) => elements.iter().all(|element| self.is_comptime_value(&element.data)),
+            Node::StructLiteral(_, fields) => fields.iter().all(|(_, value)| self.is_comptime_value(&value.data)),
+            _ => false,
+        }
+    }
+
+    fn calls_comptime_function(&self, node: &Node) -> Option<bool> {
+        return match node {
+            Node::Value(_) => Some(false),
+            Node::BinaryOperation(left, _, right) => Some(self.calls_comptime_function(&left.data)? | self.calls_comptime_function(&right.data)?),
+            Node::UnaryOperation(_, value) | Node::Casting(value, _) => self.calls_comptime_function(&value.data),
+            Node::FunctionCall(name, params) if self.comptime_functions.iter().any(|(f_name, _)| *f_name == name.data) => {
+                for param in params.iter() {
+                    self.calls_comptime_function(&param.data)?;
+                }
+                Some(true)
+            }
+            Node::ArrayLiteral(_, elements) => {
+                let mut calls = false;
+                for element in elements.iter() {
+                    calls |= self.calls_comptime_function(&element.data)?;
+                }
+                Some(calls)
+            }
+            Node::Index(value, index) => Some(self.calls_comptime_function(&value.data)? | self.calls_comptime_function(&index.data)?),
+            _ => None,
+        }
+    }
+
+    fn optimize_block(&mut self, kind: BlockKind, body: Vec<Positioned<Node>>) -> Result<Vec<Positioned<Node>>, Positioned<OptimizerError>> {
+        // Set Scope
+        self.scope = Scope::Block {
+            parent: Box::new(self.scope.clone()),
+            kind,
+            variables: vec![]
+        };
+
+        let mut new_body = Vec::new();
+        for node in body.iter() {
+            if let Some(node) = self.optimize_node(node.clone())?.1 {
+                new_body.push(node);
+            }
+        }
+
+        // Remove Scope
+        self.scope = self.scope.parent();
+
+        return Ok(new_body);
+    }
+
+    fn optimize_defer(&mut self, position: Positioned<()>, statement: Positioned<Node>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        if self.scope.return_type().is_none() {
+            return Err(position.convert(OptimizerError::DeferNotAllowed));
+        }
+        let mut body = self.optimize_block(BlockKind::Defer, vec![statement])?;
+
+        // `defer defer x` runs `x` right at the end of the deferred statement, like `defer x`
+        let mut statement = body.remove(0);
+        while let Node::Defer(inner) = statement.data {
+            statement = *inner;
+        }
+        return Ok((None, Some(position.convert(Node::Defer(Box::new(statement))))));
+    }
+
+    fn optimize_if(&mut self, position: Positioned<()>, branches: Vec<(Positioned<Node>, Vec<Positioned<Node>>)>, else_body: Option<Vec<Positioned<Node>>>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        let mut new_branches = Vec::new();
+        for (condition, body) in branches {
+            let condition_result = self.optimize_node(condition.clone())?;
+            match condition_result.0.clone().unwrap() {
+                DataType::Bool | DataType::ComptimeBool => {}
+                data_type => return Err(condition.convert(OptimizerError::IncompatibleTypes(DataType::Bool, data_type))),
+            }
+            let new_body = self.optimize_block(BlockKind::Branch, body)?;
+            new_branches.push((condition_result.1.unwrap(), new_body));
+        }
+
+        let new_else_body = if let Some(else_body) = else_body {
+            Some(self.optimize_block(BlockKind::Branch, else_body)?)
+        } else {
+            None
+        };
+
+        return Ok((None, Some(position.convert(Node::If(new_branches, new_else_body)))));
+    }
+
+    fn optimize_while(&mut self, position: Positioned<()>, condition: Positioned<Node>, body: Vec<Positioned<Node>>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        let condition_result = self.optimize_node(condition.clone())?;
+        match condition_result.0.clone().unwrap() {
+            DataType::Bool | DataType::ComptimeBool => {}
+            data_type => return Err(condition.convert(OptimizerError::IncompatibleTypes(DataType::Bool, data_type))),
+        }
+        let new_body = self.optimize_block(BlockKind::Loop, body)?;
+
+        return Ok((None, Some(position.convert(Node::While(Box::new(condition_result.1.unwrap()), new_body)))));
+    }
+
+    fn optimize_for(&mut self, position: Positioned<()>, name: Positioned<String>, range_start: Positioned<Node>, range_end: Positioned<Node>, inclusive: bool, body: Vec<Positioned<Node>>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        let start_result = self.optimize_node(range_start.clone())?;
+        let end_result = self.optimize_node(range_end.clone())?;
+        let start_type = start_result.0.unwrap();
+        let end_type = end_result.0.unwrap();
+
+        // Type the loop variable from the bounds
+        let mut data_type = if start_type.is_convertible(end_type.clone()) {
+            end_type.clone()
+        } else if end_type.is_convertible(start_type.clone()) {
+            start_type.clone()
+        } else {
+            return Err(Positioned::new(OptimizerError::IncompatibleTypes(start_type, end_type), range_start.start.clone(), range_end.end.clone()));
+        };
+        if data_type == DataType::ComptimeNumber {
+            data_type = DataType::I32;
+        }
+        if !data_type.is_integer() {
+            return Err(Positioned::new(OptimizerError::IncompatibleTypes(DataType::I32, data_type), range_start.start.clone(), range_end.end.clone()));
+        }
+        let data_type = Positioned::new(data_type, range_start.start.clone(), range_end.end.clone());
+        let r_range_start = self.coerce(start_result.1.unwrap(), &start_type, &data_type.data)?;
+        let r_range_end = self.coerce(end_result.1.unwrap(), &end_type, &data_type.data)?;
+
+        // Set Scope
+        self.scope = Scope::Block {
+            parent: Box::new(self.scope.clone()),
+            kind: BlockKind::Loop,
+            variables: vec![]
+        };
+        let c_name = self.declare_variable(name.convert(VariableData {
+            name: name.clone(),
+            var_type: name.convert(VarType::Let),
+            data_type: data_type.clone(),
+            initialized: true,
+            c_name: name.data.clone(),
+            comptime_value: None,
+        }))?;
+
+        let new_body = self.optimize_block(BlockKind::Loop, body)?;
+
+        // Remove Scope
+        self.scope = self.scope.parent();
+
+        return Ok((None, Some(position.convert(Node::For(name.convert(c_name), Some(data_type), Box::new(r_range_start), Box::new(r_range_end), inclusive, new_body)))));
+    }
+
+    fn get_struct_fields(&mut self, name: String) -> Vec<(Positioned<String>, Positioned<DataType>)> {
+        return if let Some(TypeData::Struct { fields, .. }) = self.scope.get_type(name) {
+            fields.clone()
+        } else {
+            vec![]
+        }
+    }
+
+    fn get_field_type(&mut self, struct_name: String, field: Positioned<String>) -> Result<Positioned<DataType>, Positioned<OptimizerError>> {
+        for (f_name, f_type) in self.get_struct_fields(struct_name.clone()) {
+            if f_name.data == field.data {
+                return self.resolve_type(f_type);
+            }
+        }
+        return Err(field.convert(OptimizerError::FieldNotFound(DataType::Struct(struct_name), field.data.clone())));
+    }
+
+    fn get_union_variants(&mut self, name: String) -> Vec<(Positioned<String>, Vec<Positioned<DataType>>)> {
+        return if let Some(TypeData::Union { variants, .. }) = self.scope.get_type(name) {
+            variants.clone()
+        } else {
+            vec![]
+        }
+    }
+
+    fn get_variant_payload(&mut self, union_name: String, variant: Positioned<String>) -> Result<Vec<Positioned<DataType>>, Positioned<OptimizerError>> {
+        for (v_name, v_payload) in self.get_union_variants(union_name.clone()) {
+            if v_name.data == variant.data {
+                let mut payload = vec![];
+                for p_type in v_payload {
+                    payload.push(self.resolve_type(p_type)?);
+                }
+                return Ok(payload);
+            }
+        }
+        return Err(variant.convert(OptimizerError::FieldNotFound(DataType::Union(union_name), variant.data.clone())));
+    }
+
+    fn contains_type(&mut self, data_type: Positioned<DataType>, target: &String, visited: &mut Vec<String>) -> Result<bool, Positioned<OptimizerError>> {
+        let (name, members) = match self.resolve_type(data_type)?.data {
+            DataType::Struct(name) => (name.clone(), self.get_struct_fields(name).into_iter().map(|(_, f_type)| f_type).collect::<Vec<_>>()),
+            DataType::Union(name) => (name.clone(), self.get_union_variants(name).into_iter().flat_map(|(_, payload)| payload).collect::<Vec<_>>()),
+            DataType::Array(inner, _) => return self.contains_type(*inner, target, visited),
+            _ => return Ok(false),
+        };
+        if name == *target {
+            return Ok(true);
+        }
+        if visited.contains(&name) {
+            return Ok(false);
+        }
+        visited.push(name);
+        for member in members {
+            if self.contains_type(member, target, visited)? {
+                return Ok(true);
+            }
+        }
+        return Ok(false);
+    }
+
+    fn optimize_struct_definition(&mut self, position: Positioned<()>, name: Positioned<String>, fields: Vec<(Positioned<String>, Positioned<DataType>)>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        // Check & resolve fields
+        let mut f_fields: Vec<(Positioned<String>, Positioned<DataType>)> = vec![];
+        for (f_name, f_type) in fields.iter() {
+            for (other_name, _) in f_fields.iter() {
+                if other_name.data == f_name.data {
+                    return Err(f_name.convert(OptimizerError::DuplicateField(f_name.data.clone())));
+                }
+            }
+            let f_type = self.resolve_type(f_type.clone())?;
+            if self.contains_type(f_type.clone(), &name.data, &mut vec![])? {
+                return Err(f_type.convert(OptimizerError::RecursiveType(name.data.clone())));
+            }
+            f_fields.push((f_name.clone(), f_type));
+        }
+
+        // Update type symbol (declared ahead of time by `optimize`)
+        if let Some(TypeData::Struct { fields, .. }) = self.scope.get_type(name.data.clone()) {
+            *fields = f_fields.clone();
+        }
+
+        return Ok((None, Some(position.convert(Node::StructDefinition(name, f_fields)))));
+    }
+
+    fn optimize_struct_literal(&mut self, position: Positioned<()>, name: Positioned<String>, fields: Vec<(Positioned<String>, Positioned<Node>)>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        let name = self.resolve_type_name(name)?;
+        if self.scope.get_type(name.data.clone()).is_none() {
+            return Err(name.convert(OptimizerError::TypeNotFound(name.data.clone())));
+        }
+
+        // Check given fields
+        let mut r_fields: Vec<(Positioned<String>, Positioned<Node>)> = vec![];
+        for (f_name, f_value) in fields.iter() {
+            for (other_name, _) in r_fields.iter() {
+                if other_name.data == f_name.data {
+                    return Err(f_name.convert(OptimizerError::DuplicateField(f_name.data.clone())));
+                }
+            }
+            let f_type = self.get_field_type(name.data.clone(), f_name.clone())?;
+            let result = self.optimize_node(f_value.clone())?;
+            if !result.0.clone().unwrap().is_convertible(f_type.data.clone()) {
+                return Err(f_value.convert(OptimizerError::IncompatibleTypes(f_type.data, result.0.unwrap())));
+            }
+            self.check_array_copy(&f_type.data, result.1.as_ref().unwrap())?;
+            r_fields.push((f_name.clone(), self.coerce(result.1.unwrap(), result.0.as_ref().unwrap(), &f_type.data)?));
+        }
+
+        // Check missing fields
+        for (f_name, _) in self.get_struct_fields(name.data.clone()) {
+            if !r_fields.iter().any(|(r_name, _)| r_name.data == f_name.data) {
+                return Err(position.convert(OptimizerError::MissingField(f_name.data)));
+            }
+        }
+
+        return Ok((Some(DataType::Struct(name.data.clone())), Some(position.convert(Node::StructLiteral(name, r_fields)))));
+    }
+
+    fn optimize_type_alias(&mut self, position: Positioned<()>, name: Positioned<String>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        if !matches!(self.scope, Scope::Root { .. }) {
+            return Err(position.convert(OptimizerError::TypeDefinitionNotAllowed));
+        }
+
+        // Resolving through the alias checks its target
+        let data_type = self.resolve_type(name.convert(DataType::Custom(name.data.clone())))?;
+        return Ok((None, Some(position.convert(Node::TypeAlias(name, data_type)))));
+    }
+
+    fn optimize_union_definition(&mut self, position: Positioned<()>, name: Positioned<String>, variants: Vec<(Positioned<String>, Vec<Positioned<DataType>>)>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        // Check & resolve variants
+        let mut f_variants: Vec<(Positioned<String>, Vec<Positioned<DataType>>)> = vec![];
+        for (v_name, v_payload) in variants.iter() {
+            for (other_name, _) in f_variants.iter() {
+                if other_name.data == v_name.data {
+                    return Err(v_name.convert(OptimizerError::DuplicateVariant(v_name.data.clone())));
+                }
+            }
+            let mut payload = vec![];
+            for p_type in v_payload.iter() {
+                let p_type = self.resolve_type(p_type.clone())?;
+                if self.contains_type(p_type.clone(), &name.data, &mut vec![])? {
+                    return Err(p_type.convert(OptimizerError::RecursiveType(name.data.clone())));
+                }
+                payload.push(p_type);
+            }
+            f_variants.push((v_name.clone(), payload));
+        }
+
+        // Update type symbol (declared ahead of time by `optimize`)
+        if let Some(TypeData::Union { variants, .. }) = self.scope.get_type(name.data.clone()) {
+            *variants = f_variants.clone();
+        }
+
+        return Ok((None, Some(position.convert(Node::UnionDefinition(name, f_variants)))));
+    }
+
+    fn optimize_union_literal(&mut self, position: Positioned<()>, name: Positioned<String>, variant: Positioned<String>, args: Vec<Positioned<Node>>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        let payload = self.get_variant_payload(name.data.clone(), variant.clone())?;
+        if payload.len() != args.len() {
+            return Err(position.convert(OptimizerError::IncorrectParameterCount(payload.len(), args.len())));
+        }
+
+        let mut r_args = vec![];
+        for (arg, p_type) in args.iter().zip(payload.into_iter()) {
+            let result = self.optimize_node(arg.clone())?;
+            if !result.0.clone().unwrap().is_convertible(p_type.data.clone()) {
+                return Err(arg.convert(OptimizerError::IncompatibleTypes(p_type.data, result.0.unwrap())));
+            }
+            self.check_array_copy(&p_type.data, result.1.as_ref().unwrap())?;
+            r_args.push(self.coerce(result.1.unwrap(), result.0.as_ref().unwrap(), &p_type.data)?);
+        }
+
+        return Ok((Some(DataType::Union(name.data.clone())), Some(position.convert(Node::UnionLiteral(name, variant, r_args)))));
+    }
+
+    fn optimize_member_call(&mut self, position: Positioned<()>, value: Positioned<Node>, name: Positioned<String>, params: Vec<Positioned<Node>>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        // Union variant with a payload
+        if let Node::VariableCall(id) = value.data.clone() {
+            if self.scope.get_variable(id.clone()).is_none() {
+                let id = self.resolve_type_name(value.convert(id))?.data;
+                if let Some(TypeData::Union { .. }) = self.scope.get_type(id.clone()) {
+                    return self.optimize_union_literal(position, value.convert(id), name, params);
+                }
+            }
+        }
+
+        // Method of a user type, also callable through a reference
+        let value_result = self.optimize_node(value.clone())?;
+        let value_type = value_result.0.clone().unwrap();
+        let receiver = match value_type.clone() {
+            DataType::Ref(inner) | DataType::ConstRef(inner) => inner.data,
+            data_type => data_type,
+        };
+        let function = match receiver {
+            DataType::Struct(type_name) |
+            DataType::Enum(type_name) |
+            DataType::Union(type_name) => self.scope.get_function(Some(type_name), name.data.clone()).cloned(),
+            _ => None,
+        };
+        return if let Some(function) = function {
+            let receiver_value = self.receiver_argument(value, value_result.1.unwrap(), &value_type, &function.params[0].1.data)?;
+            let mut r_params = vec![receiver_value];
+            r_params.extend(params);
+            self.check_function_call(position, name, function, r_params)
+        } else {
+            Err(name.convert(OptimizerError::MethodNotFound(value_type, name.data.clone())))
+        }
+    }
+
+    fn receiver_argument(&mut self, value: Positioned<Node>, optimized: Positioned<Node>, value_type: &DataType, self_type: &DataType) -> Result<Positioned<Node>, Positioned<OptimizerError>> {
+        // Take or drop the reference `self` expects
+        let by_ref = matches!(value_type, DataType::Ref(_) | DataType::ConstRef(_));
+        return match self_type {
+            DataType::Ref(_) if !by_ref => {
+                self.check_assignable(&optimized)?;
+                Ok(value.convert(Node::UnaryOperation(value.convert(Operator::Ref), Box::new(value.clone()))))
+            }
+            DataType::ConstRef(_) if !by_ref => Ok(value.convert(Node::UnaryOperation(value.convert(Operator::ConstRef), Box::new(value.clone())))),
+            DataType::Ref(_) | DataType::ConstRef(_) => Ok(value),
+            _ if by_ref => Ok(value.convert(Node::UnaryOperation(value.convert(Operator::Deref), Box::new(value.clone())))),
+            _ => Ok(value),
+        }
+    }
+
+    fn optimize_impl(&mut self, name: Positioned<String>, methods: Vec<Positioned<Node>>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        // Methods belong to the resolved type, `impl` on an alias extends the type it names
+        let receiver = match self.resolve_type(name.convert(DataType::Custom(name.data.clone())))?.data {
+            DataType::Struct(receiver) | DataType::Enum(receiver) | DataType::Union(receiver) => receiver,
+            _ => return Err(name.convert(OptimizerError::TypeNotFound(name.data.clone()))),
+        };
+
+        // Methods are emitted as free functions
+        self.receiver = Some(receiver);
+        for method in methods {
+            let result = self.optimize_node(method);
+            match result {
+                Ok((_, Some(node))) => self.nodes.push(node),
+                Ok((_, None)) => {}
+                Err(err) => {
+                    self.receiver = None;
+                    return Err(err);
+                }
+            }
+        }
+        self.receiver = None;
+
+        return Ok((None, None));
+    }
+
+    fn optimize_match(&mut self, position: Positioned<()>, value: Positioned<Node>, arms: Vec<(Positioned<String>, Vec<(Positioned<String>, Option<Positioned<DataType>>)>, Vec<Positioned<Node>>)>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        let value_result = self.optimize_node(value.clone())?;
+        let (union_name, r_value) = match value_result.0.clone().unwrap() {
+            DataType::Union(name) => (name, value_result.1.unwrap()),
+            DataType::Ref(inner) | DataType::ConstRef(inner) => {
+                // Match through a reference
+                if let DataType::Union(name) = inner.data {
+                    (name, value.convert(Node::UnaryOperation(value.convert(Operator::Deref), Box::new(value_result.1.unwrap()))))
+                } else {
+                    return Err(value.convert(OptimizerError::InvalidMatch(value_result.0.unwrap())));
+                }
+            }
+            data_type => return Err(value.convert(OptimizerError::InvalidMatch(data_type))),
+        };
+
+        // Check arms
+        let mut r_arms: Vec<(Positioned<String>, Vec<(Positioned<String>, Option<Positioned<DataType>>)>, Vec<Positioned<Node>>)> = vec![];
+        for (variant, bindings, body) in arms {
+            if r_arms.iter().any(|(other, _, _)| other.data == variant.data) {
+                return Err(variant.convert(OptimizerError::DuplicateVariant(variant.data.clone())));
+            }
+            let payload = self.get_variant_payload(union_name.clone(), variant.clone())?;
+            if payload.len() != bindings.len() {
+                return Err(variant.convert(OptimizerError::IncorrectParameterCount(payload.len(), bindings.len())));
+            }
+
+            // Set Scope
+            self.scope = Scope::Block {
+                parent: Box::new(self.scope.clone()),
+                kind: BlockKind::Branch,
+                variables: vec![]
+            };
+
+            // Bind the payload, `_` discards a value
+            let mut r_bindings = vec![];
+            for ((b_name, _), p_type) in bindings.into_iter().zip(payload.into_iter()) {
+                if b_name.data != "_" {
+                    let c_name = self.declare_variable(b_name.convert(VariableData {
+                        name: b_name.clone(),
+                        var_type: b_name.convert(VarType::Let),
+                        data_type: p_type.clone(),
+                        initialized: true,
+                        c_name: b_name.data.clone(),
+                        comptime_value: None,
+                    }))?;
+                    r_bindings.push((b_name.convert(c_name), Some(p_type)));
+                } else {
+                    r_bindings.push((b_name, Some(p_type)));
+                }
+            }
+
+            let new_body = self.optimize_block(BlockKind::Branch, body)?;
+
+            // Remove Scope
+            self.scope = self.scope.parent();
+
+            r_arms.push((variant, r_bindings, new_body));
+        }
+
+        // Check exhaustiveness
+        let missing: Vec<String> = self.get_union_variants(union_name.clone()).into_iter()
+            .filter(|(v_name, _)| !r_arms.iter().any(|(variant, _, _)| variant.data == v_name.data))
+            .map(|(v_name, _)| v_name.data)
+            .collect();
+        if !missing.is_empty() {
+            return Err(position.convert(OptimizerError::NonExhaustiveMatch(missing)));
+        }
+
+        return Ok((None, Some(position.convert(Node::Match(Box::new(r_value), Some(union_name), r_arms)))));
+    }
+
+    fn optimize_switch(&mut self, position: Positioned<()>, value: Positioned<Node>, cases: Vec<(Vec<(Positioned<Node>, Option<(Positioned<Node>, bool)>)>, Vec<Positioned<Node>>)>, else_body: Option<Vec<Positioned<Node>>>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        let value_result = self.optimize_node(value.clone())?;
+        let value_type = value_result.0.unwrap().default_type();
+        match &value_type {
+            DataType::Char | DataType::Enum(_) => {}
+            data_type if data_type.integer_range().is_some() => {}
+            data_type => return Err(value.convert(OptimizerError::InvalidSwitch(data_type.clone()))),
+        }
+
+        // Check cases as inclusive ranges, enum variants by their value
+        let mut ranges: Vec<(i128, i128)> = vec![];
+        let mut r_cases = vec![];
+        for (patterns, body) in cases {
+            let mut r_patterns = vec![];
+            for (pattern, range_end) in patterns {
+                let (start, r_pattern) = self.optimize_switch_pattern(&value_type, pattern.clone())?;
+                if let Node::EnumVariant(_, variant) = &r_pattern.data {
+                    if range_end.is_some() {
+                        return Err(pattern.convert(OptimizerError::InvalidCaseRange(value_type)));
+                    }
+                    if ranges.iter().any(|(other_start, other_end)| start <= *other_end && *other_start <= start) {
+                        return Err(pattern.convert(OptimizerError::OverlappingCase(format!("'{}' ({})", variant.data, start))));
+                    }
+                    ranges.push((start, start));
+                    r_patterns.push((r_pattern, None));
+                    continue;
+                }
+
+                let (end, r_pattern) = match range_end {
+                    Some((range_end, inclusive)) => {
+                        let (end, _) = self.optimize_switch_pattern(&value_type, range_end.clone())?;
+                        let last = if inclusive { end } else { end - 1 };
+                        if last < start {
+                            return Err(Positioned::new(OptimizerError::InvalidRange(start, end), pattern.start.clone(), range_end.end.clone()));
+                        }
+                        let r_start = pattern.convert(Node::Value(ValueNode::Number(start.to_string())));
+                        let r_end = range_end.convert(Node::Value(ValueNode::Number(last.to_string())));
+                        (last, Positioned::new((r_start, Some((r_end, true))), pattern.start.clone(), range_end.end.clone()))
+                    }
+                    None => (start, pattern.convert((r_pattern, None))),
+                };
+                if ranges.iter().any(|(other_start, other_end)| start <= *other_end && *other_start <= end) {
+                    let case = if start == end { start.to_string() } else { format!("{}..={}", start, end) };
+                    return Err(r_pattern.convert(OptimizerError::OverlappingCase(case)));
+                }
+                ranges.push((start, end));
+                r_patterns.push(r_pattern.data);
+            }
+
+            let new_body = self.optimize_block(BlockKind::Branch, body)?;
+            r_cases.push((r_patterns, new_body));
+        }
+        let r_else_body = match else_body {
+            Some(else_body) => Some(self.optimize_block(BlockKind::Branch, else_body)?),
+            None => None,
+        };
+
+        let value_type = value.convert(value_type);
+        return Ok((None, Some(position.convert(Node::Switch(Box::new(value_result.1.unwrap()), Some(value_type), r_cases, r_else_body)))));
+    }
+
+    fn optimize_switch_pattern(&mut self, value_type: &DataType, pattern: Positioned<Node>) -> Result<(i128, Positioned<Node>), Positioned<OptimizerError>> {
+        let result = self.optimize_node(pattern.clone())?;
+        let (pattern_type, r_pattern) = (result.0.unwrap(), result.1.unwrap());
+        if !pattern_type.is_convertible(value_type.clone()) {
+            return Err(pattern.convert(OptimizerError::IncompatibleTypes(value_type.clone(), pattern_type)));
+        }
+        Self::check_integer_range(&r_pattern, &pattern_type, value_type)?;
+
+        // Cases are compared by their numeric value
+        let number = match &r_pattern.data {
+            Node::Value(ValueNode::Char(chr)) => Self::decode_char(chr).map(|chr| chr as i128),
+            Node::EnumVariant(name, variant) => Some(self.enum_variant_value(name, variant)?),
+            data => Self::literal_integer(data),
+        };
+        return match number {
+            Some(number) => Ok((number, r_pattern)),
+            None => Err(pattern.convert(OptimizerError::ComptimeValueRequired)),
+        }
+    }
+
+    fn evaluate_comptime_integer(&mut self, value: Positioned<Node>) -> Result<i128, Positioned<OptimizerError>> {
+        let result = self.optimize_node(value.clone())?;
+        return match result.1.unwrap().data {
+            Node::Value(ValueNode::Number(num)) => num.parse::<i128>().map_err(|err| value.convert(OptimizerError::InvalidNumber(num, err))),
+            Node::UnaryOperation(Positioned { data: Operator::Minus, .. }, inner) => {
+                if let Node::Value(ValueNode::Number(num)) = inner.data {
+                    num.parse::<i128>().map(|num| -num).map_err(|err| value.convert(OptimizerError::InvalidNumber(num, err)))
+                } else {
+                    Err(value.convert(OptimizerError::ComptimeValueRequired))
+                }
+            }
+            _ => Err(value.convert(OptimizerError::ComptimeValueRequired)),
+        }
+    }
+
+    fn enum_variant_value(&mut self, name: &Positioned<String>, variant: &Positioned<String>) -> Result<i128, Positioned<OptimizerError>> {
+        // Used before its definition, evaluate the variant values now
+        if !self.evaluated_enums.contains(&name.data) {
+            let definition = self.ast.iter().find_map(|node| match node.data.clone() {
+                Node::EnumDefinition(e_name, backing_type, variants) if e_name.data == name.data => Some((e_name, backing_type, variants)),
+                _ => None,
+            });
+            if let Some((e_name, backing_type, variants)) = definition {
+                self.evaluate_enum(e_name, backing_type, variants)?;
+            }
+        }
+
+        return match self.scope.get_type(name.data.clone()) {
+            Some(TypeData::Enum { variants, .. }) => match variants.iter().find(|(v_name, _)| v_name.data == variant.data) {
+                Some((_, value)) => Ok(*value),
+                None => panic!("Should not happen!"),
+            },
+            _ => panic!("Should not happen!"),
+        }
+    }
+
+    fn optimize_enum_definition(&mut self, position: Positioned<()>, name: Positioned<String>, backing_type: Option<Positioned<DataType>>, variants: Vec<(Positioned<String>, Option<Positioned<Node>>)>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        let (backing_type, f_variants) = self.evaluate_enum(name.clone(), backing_type, variants)?;
+        let r_variants = f_variants.into_iter().map(|(v_name, value)| {
+            let v_value = v_name.convert(Node::Value(ValueNode::Number(value.to_string())));
+            (v_name, Some(v_value))
+        }).collect();
+        return Ok((None, Some(position.convert(Node::EnumDefinition(name, Some(backing_type), r_variants)))));
+    }
+
+    fn evaluate_enum(&mut self, name: Positioned<String>, backing_type: Option<Positioned<DataType>>, variants: Vec<(Positioned<String>, Option<Positioned<Node>>)>) -> Result<(Positioned<DataType>, Vec<(Positioned<String>, i128)>), Positioned<OptimizerError>> {
+        let backing_type = self.resolve_type(backing_type.unwrap_or(name.convert(DataType::I32)))?;
+        let (min, max) = if let Some(range) = backing_type.data.integer_range() {
+            range
+        } else {
+            return Err(backing_type.convert(OptimizerError::IncompatibleTypes(DataType::I32, backing_type.data.clone())));
+        };
+
+        // Check & evaluate variants
+        let mut f_variants: Vec<(Positioned<String>, i128)> = vec![];
+        let mut next = 0;
+        for (v_name, v_value) in variants {
+            for (other_name, _) in f_variants.iter() {
+                if other_name.data == v_name.data {
+                    return Err(v_name.convert(OptimizerError::DuplicateVariant(v_name.data.clone())));
+                }
+            }
+            let value = if let Some(v_value) = v_value {
+                self.evaluate_comptime_integer(v_value)?
+            } else {
+                next
+            };
+            if value < min || value > max {
+                return Err(v_name.convert(OptimizerError::VariantOverflow(v_name.data.clone(), value, backing_type.data.clone())));
+            }
+            next = value + 1;
+            f_variants.push((v_name, value));
+        }
+
+        // Update type symbol (declared ahead of time by `optimize`)
+        if let Some(TypeData::Enum { backing_type: s_backing_type, variants, .. }) = self.scope.get_type(name.data.clone()) {
+            *s_backing_type = backing_type.clone();
+            *variants = f_variants.clone();
+        }
+        self.evaluated_enums.push(name.data.clone());
+
+        return Ok((backing_type, f_variants));
+    }
+
+    fn optimize_field_access(&mut self, position: Positioned<()>, value: Positioned<Node>, field: Positioned<String>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        // Enum variant
+        if let Node::VariableCall(id) = value.data.clone() {
+            if self.scope.get_variable(id.clone()).is_none() {
+                let id = self.resolve_type_name(value.convert(id))?.data;
+                if let Some(TypeData::Enum { variants, .. }) = self.scope.get_type(id.clone()) {
+                    return if variants.iter().any(|(variant, _)| variant.data == field.data) {
+                        Ok((Some(DataType::Enum(id.clone())), Some(position.convert(Node::EnumVariant(value.convert(id), field)))))
                     } else {
-                        return Err(Positioned::new(OptimizerError::IncompatibleTypes(result.0.unwrap(), p_type.data), p_name.start, p_type.end));
+                        Err(field.convert(OptimizerError::FieldNotFound(DataType::Enum(id), field.data.clone())))
+                    }
+                }
+                if let Some(TypeData::Union { .. }) = self.scope.get_type(id.clone()) {
+                    // Union variant without a payload
+                    return self.optimize_union_literal(position, value.convert(id), field, vec![]);
+                }
+            }
+        }
+
+        let value_result = self.optimize_node(value.clone())?;
+        return match value_result.0.clone().unwrap() {
+            DataType::Struct(name) => {
+                let f_type = self.get_field_type(name, field.clone())?;
+                Ok((Some(f_type.data), Some(position.convert(Node::FieldAccess(Box::new(value_result.1.unwrap()), field)))))
+            }
+            DataType::Slice(inner) => {
+                let f_type = self.get_slice_field(DataType::Slice(inner.clone()), *inner, field.clone())?;
+                Ok((Some(f_type), Some(position.convert(Node::FieldAccess(Box::new(value_result.1.unwrap()), field)))))
+            }
+            DataType::String => {
+                let f_type = self.get_string_field(field.clone())?;
+                Ok((Some(f_type), Some(position.convert(Node::FieldAccess(Box::new(value_result.1.unwrap()), field)))))
+            }
+            DataType::Ref(inner) | DataType::ConstRef(inner) => {
+                // Access through a reference
+                let deref = value.convert(Node::UnaryOperation(value.convert(Operator::Deref), Box::new(value_result.1.clone().unwrap())));
+                match inner.data {
+                    DataType::Struct(name) => {
+                        let f_type = self.get_field_type(name, field.clone())?;
+                        Ok((Some(f_type.data), Some(position.convert(Node::FieldAccess(Box::new(deref), field)))))
+                    }
+                    DataType::Slice(slice_inner) => {
+                        let f_type = self.get_slice_field(DataType::Slice(slice_inner.clone()), *slice_inner, field.clone())?;
+                        Ok((Some(f_type), Some(position.convert(Node::FieldAccess(Box::new(deref), field)))))
+                    }
+                    DataType::String => {
+                        let f_type = self.get_string_field(field.clone())?;
+                        Ok((Some(f_type), Some(position.convert(Node::FieldAccess(Box::new(deref), field)))))
+                    }
+                    _ => Err(field.convert(OptimizerError::FieldNotFound(value_result.0.unwrap(), field.data.clone()))),
+                }
+            }
+            data_type => Err(field.convert(OptimizerError::FieldNotFound(data_type, field.data.clone()))),
+        }
+    }
+
+    fn optimize_array_literal(&mut self, position: Positioned<()>, elements: Vec<Positioned<Node>>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        // The element type is the one every element converts to
+        let mut data_type: Option<DataType> = None;
+        let mut r_elements = vec![];
+        let mut e_types = vec![];
+        for element in elements.iter() {
+            let result = self.optimize_node(element.clone())?;
+            let e_type = result.0.unwrap();
+            e_types.push(e_type.clone());
+            data_type = match data_type {
+                None => Some(e_type),
+                Some(data_type) if e_type.is_convertible(data_type.clone()) => Some(data_type),
+                Some(data_type) if data_type.is_convertible(e_type.clone()) => Some(e_type),
+                Some(data_type) => return Err(element.convert(OptimizerError::IncompatibleTypes(data_type, e_type))),
+            };
+            self.check_array_copy(data_type.as_ref().unwrap(), result.1.as_ref().unwrap())?;
+            r_elements.push(result.1.unwrap());
+        }
+
+        return if let Some(data_type) = data_type {
+            for (element, e_type) in r_elements.iter().zip(e_types.iter()) {
+                Self::check_integer_range(element, e_type, &data_type)?;
+            }
+            let data_type = position.convert(data_type);
+            Ok((Some(DataType::Array(Box::new(data_type.clone()), r_elements.len())), Some(position.convert(Node::ArrayLiteral(Some(data_type), r_elements)))))
+        } else {
+            Err(position.convert(OptimizerError::MissingType))
+        }
+    }
+
+    fn literal_integer(node: &Node) -> Option<i128> {
+        return match node {
+            Node::Value(ValueNode::Number(num)) => num.parse::<i128>().ok(),
+            Node::UnaryOperation(Positioned { data: Operator::Minus, .. }, inner) => Self::literal_integer(&inner.data).map(|num| -num),
+            _ => None,
+        }
+    }
+
+    fn optimize_index(&mut self, position: Positioned<()>, value: Positioned<Node>, index: Positioned<Node>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        let value_result = self.optimize_node(value.clone())?;
+        let (data_type, size, r_value) = match value_result.0.clone().unwrap() {
+            DataType::Array(inner, size) => (inner.data, Some(size), value_result.1.unwrap()),
+            DataType::Slice(inner) => (inner.data, None, value_result.1.unwrap()),
+            DataType::Ref(inner) | DataType::ConstRef(inner) => {
+                // Index through a reference
+                let deref = value.convert(Node::UnaryOperation(value.convert(Operator::Deref), Box::new(value_result.1.unwrap())));
+                match inner.data {
+                    DataType::Array(inner, size) => (inner.data, Some(size), deref),
+                    DataType::Slice(inner) => (inner.data, None, deref),
+                    _ => return Err(value.convert(OptimizerError::NotIndexable(value_result.0.unwrap()))),
+                }
+            }
+            data_type => return Err(value.convert(OptimizerError::NotIndexable(data_type))),
+        };
+
+        let index_result = self.optimize_node(index.clone())?;
+        let index_type = index_result.0.unwrap();
+        if index_type != DataType::ComptimeNumber && !index_type.is_integer() {
+            return Err(index.convert(OptimizerError::IncompatibleTypes(DataType::Usize, index_type)));
+        }
+        if let (Some(i), Some(size)) = (Self::literal_integer(&index_result.1.as_ref().unwrap().data), size) {
+            if i < 0 || i >= size as i128 {
+                return Err(index.convert(OptimizerError::IndexOutOfBounds(i, size)));
+            }
+        }
+
+        return if size.is_some() {
+            Ok((Some(data_type), Some(position.convert(Node::Index(Box::new(r_value), Box::new(index_result.1.unwrap()))))))
+        } else {
+            // Slices are bounds-checked at runtime
+            let data_type = position.convert(data_type);
+            Ok((Some(data_type.data.clone()), Some(position.convert(Node::SliceIndex(Box::new(r_value), Box::new(index_result.1.unwrap()), Some(data_type))))))
+        }
+    }
+
+    fn optimize_slice(&mut self, position: Positioned<()>, value: Positioned<Node>, range_start: Positioned<Node>, range_end: Positioned<Node>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        let value_result = self.optimize_node(value.clone())?;
+        let deref = value.convert(Node::UnaryOperation(value.convert(Operator::Deref), Box::new(value_result.1.clone().unwrap())));
+        let (base_type, r_value) = match value_result.0.clone().unwrap() {
+            DataType::Array(inner, size) => {
+                if let Node::ArrayLiteral(_, _) = value_result.1.as_ref().unwrap().data {} else {
+                    self.check_assignable(value_result.1.as_ref().unwrap())?;
+                }
+                (DataType::Array(inner, size), value_result.1.unwrap())
+            }
+            DataType::Slice(inner) => (DataType::Slice(inner), value_result.1.unwrap()),
+            DataType::Ref(inner) => {
+                match inner.data.clone() {
+                    DataType::Array(array_inner, size) => (DataType::Array(array_inner, size), deref),
+                    DataType::Slice(slice_inner) => (DataType::Slice(slice_inner), deref),
+                    // Pointer & length
+                    _ => (DataType::Ref(inner), value_result.1.unwrap()),
+                }
+            }
+            data_type => return Err(value.convert(OptimizerError::NotIndexable(data_type))),
+        };
+        let data_type = match base_type.clone() {
+            DataType::Array(inner, _) | DataType::Slice(inner) | DataType::Ref(inner) => inner,
+            _ => panic!("Should not happen!"),
+        };
+
+        // Bounds
+        let mut r_bounds = vec![];
+        for bound in [range_start.clone(), range_end.clone()] {
+            let bound_result = self.optimize_node(bound.clone())?;
+            let bound_type = bound_result.0.unwrap();
+            if bound_type != DataType::ComptimeNumber && !bound_type.is_integer() {
+                return Err(bound.convert(OptimizerError::IncompatibleTypes(DataType::Usize, bound_type)));
+            }
+            r_bounds.push(bound_result.1.unwrap());
+        }
+        let r_range_end = r_bounds.pop().unwrap();
+        let r_range_start = r_bounds.pop().unwrap();
+        let literal_start = Self::literal_integer(&r_range_start.data);
+        let literal_end = Self::literal_integer(&r_range_end.data);
+        if let DataType::Array(_, size) = base_type {
+            for (literal, bound) in [(literal_start, &range_start), (literal_end, &range_end)] {
+                if let Some(i) = literal {
+                    if i < 0 || i > size as i128 {
+                        return Err(bound.convert(OptimizerError::IndexOutOfBounds(i, size)));
+                    }
+                }
+            }
+        }
+        if let (Some(start), Some(end)) = (literal_start, literal_end) {
+            if start > end {
+                return Err(Positioned::new(OptimizerError::InvalidRange(start, end), range_start.start.clone(), range_end.end.clone()));
+            }
+        }
+
+        let base_type = position.convert(base_type);
+        return Ok((Some(DataType::Slice(data_type)), Some(position.convert(Node::Slice(Box::new(r_value), Some(base_type), Some(Box::new(r_range_start)), Some(Box::new(r_range_end)))))));
+    }
+
+    fn get_slice_field(&mut self, data_type: DataType, inner: Positioned<DataType>, field: Positioned<String>) -> Result<DataType, Positioned<OptimizerError>> {
+        return match field.data.as_str() {
+            "len" => Ok(DataType::Usize),
+            "ptr" => Ok(DataType::Ref(Box::new(inner))),
+            _ => Err(field.convert(OptimizerError::FieldNotFound(data_type, field.data.clone()))),
+        }
+    }
+
+    fn get_string_field(&mut self, field: Positioned<String>) -> Result<DataType, Positioned<OptimizerError>> {
+        return match field.data.as_str() {
+            "len" => Ok(DataType::Usize),
+            _ => Err(field.convert(OptimizerError::FieldNotFound(DataType::String, field.data.clone()))),
+        }
+    }
+
+    fn check_assignable(&mut self, node: &Positioned<Node>) -> Result<(), Positioned<OptimizerError>> {
+        return match &node.data {
+            Node::VariableCall(name) => {
+                if let Some(variable) = self.scope.get_declared_variable(name.clone()) {
+                    match variable.var_type.data {
+                        VarType::Var => Ok(()),
+                        _ => Err(node.convert(OptimizerError::VariableCannotBeModified(variable.name.data.clone()))),
                     }
-                } else if function.list {
-                    let result = self.optimize_node(v_param.clone())?;
-                    r_params.push(result.1.unwrap());
                 } else {
-                    return Err(position.convert(OptimizerError::IncorrectParameterCount(function.params.len(), params.len())));
+                    Err(node.convert(OptimizerError::VariableNotFound(name.clone())))
                 }
             }
-            if f_params.len() == 0 {
-                Ok((Some(function.return_type.data.clone()), Some(position.convert(Node::FunctionCall(name.clone(), r_params)))))
+            Node::FieldAccess(value, _) => {
+                // The pointer and length of slices and strings are read-only
+                match self.optimize_node(*value.clone())?.0.unwrap() {
+                    DataType::Slice(_) | DataType::String => return Err(node.convert(OptimizerError::NotAssignable)),
+                    _ => {}
+                }
+                self.check_assignable(value)
+            }
+            Node::Index(value, _) => self.check_assignable(value),
+            Node::SliceIndex(_, _, _) => Ok(()),
+            Node::UnaryOperation(Positioned { data: Operator::Deref, .. }, value) => {
+                // Only mutable references can be written through
+                match self.optimize_node(*value.clone())?.0.unwrap() {
+                    DataType::Ref(_) => Ok(()),
+                    DataType::ConstRef(_) => Err(node.convert(OptimizerError::ConstReference)),
+                    _ => Err(node.convert(OptimizerError::NotAssignable)),
+                }
+            }
+            _ => Err(node.convert(OptimizerError::NotAssignable)),
+        }
+    }
+
+    fn optimize_assignment(&mut self, position: Positioned<()>, target: Positioned<Node>, operator: Option<Positioned<Operator>>, value: Positioned<Node>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        let target_result = self.optimize_node(target.clone())?;
+        let deferred_init = match &target_result.1.as_ref().unwrap().data {
+            // A `let` declared without a value gets assigned later
+            Node::VariableCall(name) if operator.is_none() => {
+                self.scope.get_declared_variable(name.clone()).map_or(false, |variable| variable.var_type.data == VarType::Let && !variable.initialized)
+            }
+            _ => false,
+        };
+        if !deferred_init {
+            self.check_assignable(target_result.1.as_ref().unwrap())?;
+        }
+
+        if let DataType::Array(_, _) = target_result.0.clone().unwrap() {
+            return Err(value.convert(OptimizerError::ArrayCopy));
+        }
+
+        let value_result = self.optimize_node(value.clone())?;
+        let result_type = self.compound_type(position.clone(), &operator, target_result.0.clone().unwrap(), value_result.0.clone().unwrap())?;
+        if !result_type.is_convertible(target_result.0.clone().unwrap()) {
+            return Err(value.convert(OptimizerError::IncompatibleTypes(target_result.0.unwrap(), result_type)));
+        }
+
+        let r_value = if operator.is_none() {
+            self.coerce(value_result.1.unwrap(), value_result.0.as_ref().unwrap(), target_result.0.as_ref().unwrap())?
+        } else {
+            if !matches!(operator.as_ref().unwrap().data, Operator::LeftShift | Operator::RightShift) {
+                Self::check_integer_range(value_result.1.as_ref().unwrap(), value_result.0.as_ref().unwrap(), target_result.0.as_ref().unwrap())?;
+            }
+            value_result.1.unwrap()
+        };
+        return Ok((None, Some(position.convert(Node::Assignment(Box::new(target_result.1.unwrap()), operator, Box::new(r_value))))));
+    }
+
+    fn optimize_loop_control(&mut self, node: Positioned<Node>, statement: &str) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        return if self.scope.in_loop() {
+            Ok((None, Some(node)))
+        } else {
+            Err(node.convert(OptimizerError::OutsideOfLoop(statement.to_string())))
+        }
+    }
+
+    fn infer_generics(generics: &Vec<Positioned<String>>, param_type: &DataType, value_type: &DataType, bindings: &mut Vec<(String, DataType)>) {
+        match (param_type, value_type) {
+            (DataType::Custom(name), value_type) => {
+                if generics.iter().any(|generic| generic.data == *name) && !value_type.is_comptime() && !bindings.iter().any(|(bound, _)| bound == name) {
+                    bindings.push((name.clone(), value_type.clone()));
+                }
+            }
+            (DataType::Ref(param_inner), DataType::Ref(value_inner)) |
+            (DataType::ConstRef(param_inner), DataType::Ref(value_inner)) |
+            (DataType::ConstRef(param_inner), DataType::ConstRef(value_inner)) |
+            (DataType::Array(param_inner, _), DataType::Array(value_inner, _)) |
+            (DataType::Slice(param_inner), DataType::Slice(value_inner)) |
+            (DataType::Slice(param_inner), DataType::Array(value_inner, _)) => {
+                Self::infer_generics(generics, &param_inner.data, &value_inner.data, bindings);
+            }
+            _ => {}
+        }
+    }
+
+    fn instantiate_function(&mut self, position: Positioned<()>, function: FunctionData, params: &Vec<Positioned<Node>>) -> Result<FunctionData, Positioned<OptimizerError>> {
+        if function.params.len() != params.len() {
+            return Err(position.convert(OptimizerError::IncorrectParameterCount(function.params.len(), params.len())));
+        }
+
+        // Infer the type parameters, literals only decide the ones no other argument does
+        let mut value_types = Vec::new();
+        for param in params.iter() {
+            value_types.push(self.optimize_node(param.clone())?.0.unwrap());
+        }
+        let mut bindings = Vec::new();
+        for ((_, p_type), value_type) in function.params.iter().zip(value_types.iter()) {
+            Self::infer_generics(&function.generics, &p_type.data, value_type, &mut bindings);
+        }
+        for ((_, p_type), value_type) in function.params.iter().zip(value_types.iter()) {
+            Self::infer_generics(&function.generics, &p_type.data, &value_type.default_type(), &mut bindings);
+        }
+
+        let mut types = Vec::new();
+        let mut mangled = function.name.data.clone();
+        for generic in function.generics.iter() {
+            if let Some((_, bound)) = bindings.iter().find(|(name, _)| *name == generic.data) {
+                mangled.push_str(format!("__{}", bound.mangle()).as_str());
+                types.push(bound.clone());
             } else {
-                Err(position.convert(OptimizerError::IncorrectParameterCount(function.params.len(), params.len())))
+                return Err(position.convert(OptimizerError::TypeParameterNotInferred(generic.data.clone())));
+            }
+        }
+
+        if self.scope.get_function(function.receiver.clone(), mangled.clone()).is_none() {
+            // Instances are defined at the root, right before the definition using them
+            let scope = self.scope.clone();
+            self.scope = self.scope.root().clone();
+            let type_bindings = std::mem::replace(&mut self.type_bindings, bindings);
+            let receiver = std::mem::replace(&mut self.receiver, function.receiver.clone());
+            let result = self.optimize_function_definition(position.clone(), function.name.convert(mangled.clone()), vec![], function.params, Some(function.return_type), function.body);
+            self.type_bindings = type_bindings;
+            self.receiver = receiver;
+            let root = std::mem::replace(&mut self.scope, scope);
+
+            match result {
+                Ok((_, node)) => {
+                    *self.scope.root() = root;
+                    self.nodes.push(node.unwrap());
+                }
+                Err(err) => return Err(position.convert(OptimizerError::InvalidInstantiation(function.name.data, types, Box::new(err.data)))),
             }
+        }
+        return Ok(self.scope.get_function(function.receiver, mangled).cloned().unwrap());
+    }
+
+    fn optimize_function_call(&mut self, position: Positioned<()>, name: Positioned<String>, params: Vec<Positioned<Node>>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        // Indirect call through a variable of a function type
+        if let Some(VariableData { data_type: Positioned { data: DataType::Function(f_params, return_type), .. }, c_name, .. }) = self.scope.get_variable(name.data.clone()).cloned() {
+            let function = FunctionData {
+                name: name.convert(c_name),
+                return_type: *return_type,
+                params: f_params.into_iter().map(|p_type| (p_type.convert(String::new()), p_type)).collect(),
+                list: false,
+                generics: vec![],
+                body: vec![],
+                receiver: None,
+            };
+            return self.check_function_call(position, name, function, params);
+        }
+
+        return if let Some(function) = self.scope.get_function(None, name.data.clone()).cloned() {
+            self.check_function_call(position, name, function, params)
         } else {
             Err(position.convert(OptimizerError::FunctionNotFound(name.data)))
         }
     }
 
+    fn check_function_call(&mut self, position: Positioned<()>, name: Positioned<String>, function: FunctionData, params: Vec<Positioned<Node>>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        if function.generics.len() != 0 {
+            let instance = self.instantiate_function(position.clone(), function, &params)?;
+            return self.check_function_call(position, name, instance, params);
+        }
+
+        let mut f_params = VecDeque::new();
+        // Copy the params
+        for param in function.params.iter() {
+            f_params.push_back(param.clone());
+        }
+        // Check the given params
+        let mut r_params = Vec::new();
+        for v_param in params.iter() {
+            if let Some((p_name, p_type)) = f_params.pop_front() {
+                let result = self.optimize_node(v_param.clone())?;
+                if result.0.clone().unwrap().is_convertible(p_type.data.clone()) {
+                    r_params.push(self.coerce(result.1.unwrap(), result.0.as_ref().unwrap(), &p_type.data)?);
+                } else {
+                    return Err(Positioned::new(OptimizerError::IncompatibleTypes(p_type.data, result.0.unwrap()), p_name.start, p_type.end));
+                }
+            } else if function.list {
+                let result = self.optimize_node(v_param.clone())?;
+                r_params.push(result.1.unwrap());
+            } else {
+                return Err(position.convert(OptimizerError::IncorrectParameterCount(function.params.len(), params.len())));
+            }
+        }
+        return if f_params.len() == 0 {
+            Ok((Some(function.return_type.data.clone()), Some(position.convert(Node::FunctionCall(name.convert(function.c_name()), r_params)))))
+        } else {
+            Err(position.convert(OptimizerError::IncorrectParameterCount(function.params.len(), params.len())))
+        }
+    }
+
     fn optimize_extern_fn(&mut self, position: Positioned<()>, name: Positioned<String>, params: Vec<(Positioned<String>, Positioned<DataType>)>, list: bool, return_type: Option<Positioned<DataType>>) -> Result<(Option<DataType>, Option<Positioned<Node>>), Positioned<OptimizerError>> {
+        let (params, return_type) = self.resolve_signature(params, return_type)?;
+
         // Save function symbol
         let function_data = FunctionData {
             name: name.clone(),
             return_type: return_type.clone().unwrap_or(name.clone().convert(DataType::Void)),
             params: params.clone(),
-            list
+            list,
+            generics: vec![],
+            body: vec![],
+            receiver: None,
         };
         self.scope.add_function(position.convert(function_data));
 
@@ -469,7 +2415,9 @@ impl Optimizer {
                 name: p_name.clone(),
                 var_type: p_name.convert(VarType::FunctionParam),
                 data_type: p_type.clone(),
-                initialized: false
+                initialized: false,
+                c_name: p_name.data.clone(),
+                comptime_value: None,
             });
         }
 
@@ -498,7 +2446,11 @@ impl Optimizer {
                         let mut optimizer = Optimizer::new(str, ast);
                         let optimizer_result = optimizer.optimize(false)?;
 
-                        if let Scope::Root { functions, variables, .. } = optimizer.scope {
+                        if let Scope::Root { functions, variables, types } = optimizer.scope {
+                            for custom_type in types {
+                                self.scope.add_type(position.convert(custom_type))?;
+                            }
+
                             for function in functions {
                                 self.scope.add_function(position.convert(function));
                             }
@@ -553,15 +2505,77 @@ impl Optimizer {
             Node::VariableDefinition(var_type, name, data_type, value) => self.check_variable_definition(position, var_type, name, data_type, value),
             Node::Casting(left, right) => self.optimize_casting(*left, right),
             Node::VariableCall(id) => self.optimize_variable_call(node.convert(id)),
-            Node::VariableAssignment(_, id, value) => self.optimize_variable_assignment(id, *value),
-            Node::FunctionDefinition(name, params, return_type, body) => self.optimize_function_definition(position, name, params, return_type, body),
+            Node::FunctionDefinition(name, generics, params, return_type, body) => {
+                // Names of generic instances and methods are joined with `__`
+                Self::check_reserved_name(&name)?;
+                self.optimize_function_definition(position, name, generics, params, return_type, body)
+            }
             Node::Return(node) => self.optimize_return(*node),
             Node::FunctionCall(name, params) => self.optimize_function_call(position, name, params),
+            Node::If(branches, else_body) => self.optimize_if(position, branches, else_body),
+            Node::While(condition, body) => self.optimize_while(position, *condition, body),
+            Node::For(name, _, range_start, range_end, inclusive, body) => self.optimize_for(position, name, *range_start, *range_end, inclusive, body),
+            Node::StructDefinition(name, fields) => self.optimize_struct_definition(position, name, fields),
+            Node::EnumDefinition(name, backing_type, variants) => self.optimize_enum_definition(position, name, backing_type, variants),
+            Node::EnumVariant(_, _) => panic!("Should not happen!"),
+            Node::StructLiteral(name, fields) => self.optimize_struct_literal(position, name, fields),
+            Node::UnionDefinition(name, variants) => self.optimize_union_definition(position, name, variants),
+            Node::TypeAlias(name, _) => self.optimize_type_alias(position, name),
+            Node::UnionLiteral(_, _, _) => panic!("Should not happen!"),
+            Node::Match(value, _, arms) => self.optimize_match(position, *value, arms),
+            Node::Switch(value, _, cases, else_body) => self.optimize_switch(position, *value, cases, else_body),
+            Node::MemberCall(value, name, params) => self.optimize_member_call(position, *value, name, params),
+            Node::Impl(name, methods) => self.optimize_impl(name, methods),
+            Node::FieldAccess(value, field) => self.optimize_field_access(position, *value, field),
+            Node::ArrayLiteral(_, elements) => self.optimize_array_literal(position, elements),
+            Node::Index(value, index) => self.optimize_index(position, *value, *index),
+            Node::Slice(value, _, Some(range_start), Some(range_end)) => self.optimize_slice(position, *value, *range_start, *range_end),
+            Node::Slice(_, _, _, _) => panic!("Should not happen!"),
+            Node::SliceIndex(_, _, _) => panic!("Should not happen!"),
+            Node::Assignment(target, operator, value) => self.optimize_assignment(position, *target, operator, *value),
+            Node::Block(body) => Ok((None, Some(position.convert(Node::Block(self.optimize_block(BlockKind::Plain, body)?))))),
+            Node::Defer(statement) => self.optimize_defer(position, *statement),
+            Node::Comptime(body) => self.optimize_comptime(position, body),
+            Node::Break => self.optimize_loop_control(node, "break"),
+            Node::Continue => self.optimize_loop_control(node, "continue"),
             Node::CompilerInstruction(instruction) => self.optimize_compiler_instruction(node.convert(instruction)),
         }
     }
 
     pub fn optimize(&mut self, main: bool) -> Result<Vec<Positioned<Node>>, Positioned<OptimizerError>> {
+        // Declare types first, so they can be used before their definition
+        for node in self.ast.clone() {
+            match node.data.clone() {
+                Node::StructDefinition(name, fields) => {
+                    Self::check_reserved_name(&name)?;
+                    self.scope.add_type(node.convert(TypeData::Struct { name, fields }))?;
+                }
+                Node::EnumDefinition(name, backing_type, variants) => {
+                    Self::check_reserved_name(&name)?;
+                    for (v_name, _) in variants.iter() {
+                        Self::check_reserved_name(v_name)?;
+                    }
+                    self.scope.add_type(node.convert(TypeData::Enum {
+                        name: name.clone(),
+                        backing_type: backing_type.unwrap_or(name.convert(DataType::I32)),
+                        variants: variants.into_iter().map(|(v_name, _)| (v_name, 0)).collect(),
+                    }))?;
+                }
+                Node::UnionDefinition(name, variants) => {
+                    Self::check_reserved_name(&name)?;
+                    for (v_name, _) in variants.iter() {
+                        Self::check_reserved_name(v_name)?;
+                    }
+                    self.scope.add_type(node.convert(TypeData::Union { name, variants }))?;
+                }
+                Node::TypeAlias(name, data_type) => {
+                    Self::check_reserved_name(&name)?;
+                    self.scope.add_type(node.convert(TypeData::Alias { name, data_type }))?;
+                }
+                _ => {}
+            }
+        }
+
         while let Some(node) = self.current() {
             let result = self.optimize_node(node)?;
             if let Some(result_node) = result.1 {
@@ -574,7 +2588,7 @@ impl Optimizer {
             match &self.scope {
                 Scope::Root { functions ,.. } => {
                     for function in functions.iter() {
-                        if function.name.data == "main" {
+                        if function.name.data == "main" && function.receiver.is_none() {
                             if function.params.len() != 0 || function.return_type.data != DataType::I32 {
                                 return Err(function.name.convert(OptimizerError::MainFunctionNotCorrectlyDefined));
                             }
@@ -125,9 +125,77 @@ impl Parser {
         return Ok(Positioned::new(Node::FunctionCall(identifier, params), start, end));
     }
 
+    fn is_struct_literal(&self) -> bool {
+        // `Name { field: ...` can't be confused with a block, as no statement starts with `id :`
+        if let (Some(Positioned { data: Token::Identifier(_), .. }), Some(Positioned { data: Token::Colon, .. })) = (self.nth(2), self.nth(3)) {
+            return true;
+        }
+        return false;
+    }
+
+    fn parse_struct_literal(&mut self, identifier: Positioned<String>) -> Result<Positioned<Node>, Positioned<ParserError>> {
+        let start = identifier.start.clone();
+        self.advance();
+        self.expect_token(Token::LeftCurlyBracket)?;
+        self.advance();
+        let mut fields = vec![];
+        let end;
+        loop {
+            let mut current = self.expect_current(vec![Either::A(Token::RightCurlyBracket)])?;
+            if current.data == Token::RightCurlyBracket {
+                end = current.end.clone();
+                break;
+            }
+            if fields.len() != 0 {
+                self.expect_token(Token::Comma)?;
+                self.advance();
+                current = self.expect_current(vec![Either::A(Token::RightCurlyBracket)])?;
+                if current.data == Token::RightCurlyBracket {
+                    end = current.end.clone();
+                    break;
+                }
+            }
+            if let Token::Identifier(field_id) = current.data.clone() {
+                let field_identifier = current.convert(field_id);
+                self.advance();
+                self.expect_token(Token::Colon)?;
+                self.advance();
+                let value = self.parse_expr()?;
+                fields.push((field_identifier, value));
+            } else {
+                return Err(current.clone().convert(ParserError::UnexpectedToken(current.data, vec![Either::B("Identifier".to_string())])));
+            }
+        }
+        return Ok(Positioned::new(Node::StructLiteral(identifier, fields), start, end));
+    }
+
+    fn parse_array_literal(&mut self) -> Result<Positioned<Node>, Positioned<ParserError>> {
+        let start = self.current().unwrap().start.clone();
+        self.advance();
+        let mut elements = vec![];
+        loop {
+            let mut current = self.expect_current(vec![Either::A(Token::RightSquareBracket)])?;
+            if current.data == Token::RightSquareBracket {
+                break;
+            }
+            if elements.len() != 0 {
+                self.expect_token(Token::Comma)?;
+                self.advance();
+                current = self.expect_current(vec![Either::A(Token::RightSquareBracket)])?;
+                if current.data == Token::RightSquareBracket {
+                    break;
+                }
+            }
+            elements.push(self.parse_expr()?);
+        }
+        let end = self.current().unwrap().end.clone();
+        return Ok(Positioned::new(Node::ArrayLiteral(None, elements), start, end));
+    }
+
     fn parse_value(&mut self) -> Result<Positioned<Node>, Positioned<ParserError>> {
         return if let Some(current) = self.current() {
             match current.data.clone() {
+                Token::Number(number) if number.contains('.') => Ok(current.convert(Node::Value(ValueNode::Float(number)))),
                 Token::Number(number) => Ok(current.convert(Node::Value(ValueNode::Number(number)))),
                 Token::Char(chr) => Ok(current.convert(Node::Value(ValueNode::Char(chr)))),
                 Token::String(str) => Ok(current.convert(Node::Value(ValueNode::String(str)))),
@@ -135,6 +203,7 @@ impl Parser {
                     match keyword {
                         Keyword::True => Ok(current.convert(Node::Value(ValueNode::Boolean(true)))),
                         Keyword::False => Ok(current.convert(Node::Value(ValueNode::Boolean(false)))),
+                        Keyword::Comptime => self.parse_comptime(current.start.clone()),
                         _ => Err(current.clone().convert(ParserError::UnexpectedToken(current.data, vec![Either::B("Value".to_string())])))
                     }
                 }
@@ -144,12 +213,17 @@ impl Parser {
                     self.expect_token(Token::RightParenthesis)?;
                     return Ok(expr);
                 }
+                Token::LeftSquareBracket => self.parse_array_literal(),
                 Token::Identifier(id) => {
                     if let Some(next) = self.nth(1) {
                         if next.data == Token::LeftParenthesis {
                             // Function Call
                             return self.parse_function_call(current.clone().convert(id));
                         }
+                        if next.data == Token::LeftCurlyBracket && self.is_struct_literal() {
+                            // Struct Literal
+                            return self.parse_struct_literal(current.clone().convert(id));
+                        }
                     }
                     // Variable Call
                     return Ok(current.convert(Node::VariableCall(id)))
@@ -161,8 +235,76 @@ impl Parser {
         }
     }
 
+    fn parse_comptime(&mut self, start: Position) -> Result<Positioned<Node>, Positioned<ParserError>> {
+        self.advance();
+        let body = self.parse_block()?;
+        // Stay on the closing bracket, like any other value
+        self.rewind();
+
+        let end = self.current().unwrap().end.clone();
+        return Ok(Positioned::new(Node::Comptime(body), start, end));
+    }
+
+    fn parse_postfix(&mut self) -> Result<Positioned<Node>, Positioned<ParserError>> {
+        let mut value = self.parse_value()?;
+
+        while let Some(next) = self.nth(1) {
+            match next.data {
+                Token::Dot => {
+                    self.advance();
+                    self.advance();
+                    let current = self.expect_current(vec![Either::B("Identifier".to_string())])?;
+                    if let Token::Identifier(field) = current.data.clone() {
+                        let start = value.start.clone();
+                        if let Some(Positioned { data: Token::LeftParenthesis, .. }) = self.nth(1) {
+                            // Member Call
+                            let call = self.parse_function_call(current.convert(field))?;
+                            if let Node::FunctionCall(name, params) = call.data {
+                                value = Positioned::new(Node::MemberCall(Box::new(value), name, params), start, call.end);
+                            } else {
+                                panic!("Should not happen!");
+                            }
+                            continue;
+                        }
+                        let end = current.end.clone();
+                        value = Positioned::new(Node::FieldAccess(Box::new(value), current.convert(field)), start, end);
+                    } else {
+                        return Err(current.clone().convert(ParserError::UnexpectedToken(current.data, vec![Either::B("Identifier".to_string())])));
+                    }
+                }
+                Token::LeftSquareBracket => {
+                    self.advance();
+                    self.advance();
+                    let index = self.parse_expr()?;
+                    let start = value.start.clone();
+                    let current = self.expect_current(vec![Either::A(Token::RightSquareBracket)])?;
+                    if current.data == Token::DoubleDot || current.data == Token::DoubleDotEqual {
+                        // Slicing
+                        self.advance();
+                        let mut range_end = self.parse_expr()?;
+                        if current.data == Token::DoubleDotEqual {
+                            let one = range_end.convert(Node::Value(ValueNode::Number("1".to_string())));
+                            let plus = range_end.convert(Operator::Plus);
+                            range_end = range_end.clone().convert(Node::BinaryOperation(Box::new(range_end), plus, Box::new(one)));
+                        }
+                        self.expect_token(Token::RightSquareBracket)?;
+                        let end = self.current().unwrap().end.clone();
+                        value = Positioned::new(Node::Slice(Box::new(value), None, Some(Box::new(index)), Some(Box::new(range_end))), start, end);
+                        continue;
+                    }
+                    self.expect_token(Token::RightSquareBracket)?;
+                    let end = self.current().unwrap().end.clone();
+                    value = Positioned::new(Node::Index(Box::new(value), Box::new(index)), start, end);
+                }
+                _ => break
+            }
+        }
+
+        return Ok(value);
+    }
+
     fn parse_cast(&mut self) -> Result<Positioned<Node>, Positioned<ParserError>> {
-        let mut left = self.parse_value()?;
+        let mut left = self.parse_postfix()?;
         self.advance();
 
         while let Some(current) = self.current() {
@@ -215,7 +357,7 @@ impl Parser {
             };
             self.advance();
 
-            let value = self.parse_cast()?;
+            let value = self.parse_unary()?;
             let end = value.end.clone();
             Ok(Positioned::new(Node::UnaryOperation(operator, Box::new(value)), start, end))
         } else {
@@ -382,10 +524,73 @@ impl Parser {
             Token::Keyword(Keyword::I16) => Ok(current.convert(DataType::I16)),
             Token::Keyword(Keyword::I32) => Ok(current.convert(DataType::I32)),
             Token::Keyword(Keyword::I64) => Ok(current.convert(DataType::I64)),
+            Token::Keyword(Keyword::Usize) => Ok(current.convert(DataType::Usize)),
+            Token::Keyword(Keyword::Isize) => Ok(current.convert(DataType::Isize)),
+            Token::Keyword(Keyword::CInt) => Ok(current.convert(DataType::CInt)),
+            Token::Keyword(Keyword::CLong) => Ok(current.convert(DataType::CLong)),
+            Token::Keyword(Keyword::CSizeT) => Ok(current.convert(DataType::Usize)),
+            Token::Keyword(Keyword::CChar) => Ok(current.convert(DataType::Char)),
+            Token::Keyword(Keyword::F32) => Ok(current.convert(DataType::F32)),
+            Token::Keyword(Keyword::F64) => Ok(current.convert(DataType::F64)),
             Token::Keyword(Keyword::Str) => Ok(current.convert(DataType::String)),
             Token::Keyword(Keyword::Bool) => Ok(current.convert(DataType::Bool)),
             Token::Keyword(Keyword::Char) => Ok(current.convert(DataType::Char)),
             Token::Keyword(Keyword::Void) => Ok(current.convert(DataType::Void)),
+            Token::Keyword(Keyword::Fn) => {
+                let start = current.start.clone();
+                self.advance();
+                self.expect_token(Token::LeftParenthesis)?;
+                self.advance();
+                let mut params = vec![];
+                loop {
+                    current = self.expect_current(vec![Either::A(Token::RightParenthesis)])?;
+                    if current.data == Token::RightParenthesis {
+                        break;
+                    }
+                    if params.len() != 0 {
+                        self.expect_token(Token::Comma)?;
+                        self.advance();
+                    }
+                    params.push(self.parse_type()?);
+                    self.advance();
+                }
+                // Return type
+                let return_type = if let Some(Positioned { data: Token::Colon, .. }) = self.nth(1) {
+                    self.advance();
+                    self.advance();
+                    self.parse_type()?
+                } else {
+                    current.convert(DataType::Void)
+                };
+                let end = self.current().unwrap().end.clone();
+                Ok(Positioned::new(DataType::Function(params, Box::new(return_type)), start, end))
+            }
+            Token::Identifier(ref id) => Ok(current.convert(DataType::Custom(id.clone()))),
+            Token::LeftSquareBracket => {
+                let start = current.start.clone();
+                self.advance();
+                if let Some(Positioned { data: Token::RightSquareBracket, .. }) = self.current() {
+                    // Slice
+                    self.advance();
+                    let inner = self.parse_type()?;
+                    let end = inner.end.clone();
+                    return Ok(Positioned::new(DataType::Slice(Box::new(inner)), start, end));
+                }
+                let inner = self.parse_type()?;
+                self.advance();
+                self.expect_token(Token::Semicolon)?;
+                self.advance();
+                current = self.expect_current(vec![Either::B("array size".to_string())])?;
+                if let Token::Number(size) = current.data.clone() {
+                    if let Ok(size) = size.parse::<usize>() {
+                        self.advance();
+                        self.expect_token(Token::RightSquareBracket)?;
+                        let end = self.current().unwrap().end.clone();
+                        return Ok(Positioned::new(DataType::Array(Box::new(inner), size), start, end));
+                    }
+                }
+                Err(current.clone().convert(ParserError::UnexpectedToken(current.data, vec![Either::B("array size".to_string())])))
+            }
             Token::Keyword(Keyword::Comptime) => {
                 self.advance();
                 current = self.expect_current(vec![Either::B("type".to_string())])?;
@@ -398,6 +603,14 @@ impl Parser {
                     Token::Keyword(Keyword::I16) => Ok(current.convert(DataType::ComptimeNumber)),
                     Token::Keyword(Keyword::I32) => Ok(current.convert(DataType::ComptimeNumber)),
                     Token::Keyword(Keyword::I64) => Ok(current.convert(DataType::ComptimeNumber)),
+                    Token::Keyword(Keyword::Usize) => Ok(current.convert(DataType::ComptimeNumber)),
+                    Token::Keyword(Keyword::Isize) => Ok(current.convert(DataType::ComptimeNumber)),
+                    Token::Keyword(Keyword::CInt) => Ok(current.convert(DataType::ComptimeNumber)),
+                    Token::Keyword(Keyword::CLong) => Ok(current.convert(DataType::ComptimeNumber)),
+                    Token::Keyword(Keyword::CSizeT) => Ok(current.convert(DataType::ComptimeNumber)),
+                    Token::Keyword(Keyword::CChar) => Ok(current.convert(DataType::ComptimeChar)),
+                    Token::Keyword(Keyword::F32) => Ok(current.convert(DataType::ComptimeFloat)),
+                    Token::Keyword(Keyword::F64) => Ok(current.convert(DataType::ComptimeFloat)),
                     Token::Keyword(Keyword::Str) => Ok(current.convert(DataType::ComptimeString)),
                     Token::Keyword(Keyword::Bool) => Ok(current.convert(DataType::ComptimeBool)),
                     Token::Keyword(Keyword::Char) => Ok(current.convert(DataType::ComptimeChar)),
@@ -444,6 +657,15 @@ impl Parser {
                 let end = inner.end.clone();
                 Ok(Positioned::new(DataType::Ref(Box::new(inner)), start, end))
             }
+            Token::DoubleAnd => {
+                // `&&T` is lexed as a single token
+                let start = current.start.clone();
+                self.advance();
+                let inner = self.parse_type()?;
+                let end = inner.end.clone();
+                let inner = Positioned::new(DataType::Ref(Box::new(inner)), start.clone(), end.clone());
+                Ok(Positioned::new(DataType::Ref(Box::new(inner)), start, end))
+            }
             _ => Err(current.clone().convert(ParserError::UnexpectedToken(current.data, vec![Either::B("type".to_string())]))),
         }
     }
@@ -495,6 +717,30 @@ impl Parser {
             let identifier = current.convert(id);
             self.advance();
 
+            // Type parameters
+            let mut generics = vec![];
+            if let Some(Positioned { data: Token::LeftAngle, .. }) = self.current() {
+                self.advance();
+                loop {
+                    current = self.expect_current(vec![Either::A(Token::RightAngle)])?;
+                    if current.data.clone() == Token::RightAngle {
+                        break;
+                    }
+                    if generics.len() != 0 {
+                        self.expect_token(Token::Comma)?;
+                        self.advance();
+                        current = self.expect_current(vec![Either::B("type parameter".to_string())])?;
+                    }
+                    if let Token::Identifier(generic) = current.data.clone() {
+                        generics.push(current.convert(generic));
+                        self.advance();
+                    } else {
+                        return Err(current.clone().convert(ParserError::UnexpectedToken(current.data, vec![Either::B("type parameter".to_string())])));
+                    }
+                }
+                self.advance();
+            }
+
             // Parameters
             self.expect_token(Token::LeftParenthesis);
             self.advance();
@@ -534,26 +780,451 @@ impl Parser {
             }
 
             // Body
-            self.expect_token(Token::LeftCurlyBracket);
+            let body = self.parse_block()?;
+
+            let end = self.nth(-1).unwrap().end.clone();
+            Ok(Positioned::new(Node::FunctionDefinition(identifier, generics, params, return_type, body), start, end))
+        } else {
+            Err(current.clone().convert(ParserError::UnexpectedToken(current.data, vec![Either::B("Identifier".to_string())])))
+        }
+    }
+
+    fn parse_block(&mut self) -> Result<Vec<Positioned<Node>>, Positioned<ParserError>> {
+        self.expect_token(Token::LeftCurlyBracket)?;
+        self.advance();
+        let mut body = vec![];
+        loop {
+            let current = self.expect_current(vec![Either::A(Token::RightCurlyBracket)])?;
+            if current.data == Token::RightCurlyBracket {
+                break;
+            } else {
+                body.push(self.parse_current()?);
+            }
+        }
+        self.advance();
+        return Ok(body);
+    }
+
+    fn parse_if(&mut self, start: Position) -> Result<Positioned<Node>, Positioned<ParserError>> {
+        let mut branches = vec![];
+        let mut else_body = None;
+        loop {
+            self.advance();
+            let condition = self.parse_expr()?;
+            let body = self.parse_block()?;
+            branches.push((condition, body));
+
+            if let Some(current) = self.current() {
+                if current.data == Token::Keyword(Keyword::Else) {
+                    self.advance();
+                    let next = self.expect_current(vec![Either::A(Token::Keyword(Keyword::If)), Either::A(Token::LeftCurlyBracket)])?;
+                    if next.data == Token::Keyword(Keyword::If) {
+                        // Else if
+                        continue;
+                    }
+                    else_body = Some(self.parse_block()?);
+                }
+            }
+            break;
+        }
+
+        let end = self.nth(-1).unwrap().end.clone();
+        return Ok(Positioned::new(Node::If(branches, else_body), start, end));
+    }
+
+    fn parse_while(&mut self, start: Position) -> Result<Positioned<Node>, Positioned<ParserError>> {
+        self.advance();
+        let condition = self.parse_expr()?;
+        let body = self.parse_block()?;
+
+        let end = self.nth(-1).unwrap().end.clone();
+        return Ok(Positioned::new(Node::While(Box::new(condition), body), start, end));
+    }
+
+    fn parse_bare_block(&mut self, start: Position) -> Result<Positioned<Node>, Positioned<ParserError>> {
+        let body = self.parse_block()?;
+
+        let end = self.nth(-1).unwrap().end.clone();
+        return Ok(Positioned::new(Node::Block(body), start, end));
+    }
+
+    fn parse_for(&mut self, start: Position) -> Result<Positioned<Node>, Positioned<ParserError>> {
+        self.advance();
+        let current = self.expect_current(vec![Either::B("Identifier".to_string())])?;
+        return if let Token::Identifier(id) = current.data.clone() {
+            let identifier = current.convert(id);
+            self.advance();
+            self.expect_token(Token::Keyword(Keyword::In))?;
+            self.advance();
+
+            // Range
+            let range_start = self.parse_expr()?;
+            let current = self.expect_current(vec![Either::A(Token::DoubleDot), Either::A(Token::DoubleDotEqual)])?;
+            let inclusive = match current.data {
+                Token::DoubleDot => false,
+                Token::DoubleDotEqual => true,
+                _ => return Err(current.clone().convert(ParserError::UnexpectedToken(current.data, vec![Either::A(Token::DoubleDot), Either::A(Token::DoubleDotEqual)]))),
+            };
+            self.advance();
+            let range_end = self.parse_expr()?;
+
+            // Body
+            let body = self.parse_block()?;
+
+            let end = self.nth(-1).unwrap().end.clone();
+            Ok(Positioned::new(Node::For(identifier, None, Box::new(range_start), Box::new(range_end), inclusive, body), start, end))
+        } else {
+            Err(current.clone().convert(ParserError::UnexpectedToken(current.data, vec![Either::B("Identifier".to_string())])))
+        }
+    }
+
+    fn parse_struct_definition(&mut self, start: Position) -> Result<Positioned<Node>, Positioned<ParserError>> {
+        self.advance();
+
+        let mut current = self.expect_current(vec![Either::B("Identifier".to_string())])?;
+        return if let Token::Identifier(id) = current.data.clone() {
+            let identifier = current.convert(id);
             self.advance();
-            let mut body = vec![];
+
+            // Fields
+            self.expect_token(Token::LeftCurlyBracket)?;
+            self.advance();
+            let mut fields = vec![];
             loop {
                 current = self.expect_current(vec![Either::A(Token::RightCurlyBracket)])?;
-                if current.data.clone() == Token::RightCurlyBracket {
+                if current.data == Token::RightCurlyBracket && fields.len() != 0 {
                     break;
+                }
+                if fields.len() != 0 {
+                    self.expect_token(Token::Comma)?;
+                    self.advance();
+                    current = self.expect_current(vec![Either::A(Token::RightCurlyBracket)])?;
+                    if current.data == Token::RightCurlyBracket {
+                        break;
+                    }
+                }
+                if let Token::Identifier(field_id) = current.data.clone() {
+                    let field_identifier = current.convert(field_id);
+                    self.advance();
+                    self.expect_token(Token::Colon)?;
+                    self.advance();
+                    let data_type = self.parse_type()?;
+                    self.advance();
+                    fields.push((field_identifier, data_type));
                 } else {
-                    body.push(self.parse_current()?);
+                    return Err(current.clone().convert(ParserError::UnexpectedToken(current.data, vec![Either::B("Identifier".to_string())])));
                 }
             }
             self.advance();
 
             let end = self.nth(-1).unwrap().end.clone();
-            Ok(Positioned::new(Node::FunctionDefinition(identifier, params, return_type, body), start, end))
+            Ok(Positioned::new(Node::StructDefinition(identifier, fields), start, end))
+        } else {
+            Err(current.clone().convert(ParserError::UnexpectedToken(current.data, vec![Either::B("Identifier".to_string())])))
+        }
+    }
+
+    fn parse_type_alias(&mut self, start: Position) -> Result<Positioned<Node>, Positioned<ParserError>> {
+        self.advance();
+
+        let current = self.expect_current(vec![Either::B("Identifier".to_string())])?;
+        return if let Token::Identifier(id) = current.data.clone() {
+            let identifier = current.convert(id);
+            self.advance();
+            self.expect_token(Token::Equal)?;
+            self.advance();
+            let data_type = self.parse_type()?;
+            self.advance();
+            self.expect_token(Token::Semicolon)?;
+            self.advance();
+
+            let end = self.nth(-1).unwrap().end.clone();
+            Ok(Positioned::new(Node::TypeAlias(identifier, data_type), start, end))
         } else {
             Err(current.clone().convert(ParserError::UnexpectedToken(current.data, vec![Either::B("Identifier".to_string())])))
         }
     }
 
+    fn parse_enum_definition(&mut self, start: Position) -> Result<Positioned<Node>, Positioned<ParserError>> {
+        self.advance();
+
+        let mut current = self.expect_current(vec![Either::B("Identifier".to_string())])?;
+        return if let Token::Identifier(id) = current.data.clone() {
+            let identifier = current.convert(id);
+            self.advance();
+
+            // Backing type
+            let mut backing_type = None;
+            current = self.expect_current(vec![Either::A(Token::LeftCurlyBracket)])?;
+            if current.data == Token::Colon {
+                self.advance();
+                backing_type = Some(self.parse_type()?);
+                self.advance();
+            }
+
+            // Variants
+            self.expect_token(Token::LeftCurlyBracket)?;
+            self.advance();
+            let mut variants = vec![];
+            loop {
+                current = self.expect_current(vec![Either::A(Token::RightCurlyBracket)])?;
+                if current.data == Token::RightCurlyBracket && variants.len() != 0 {
+                    break;
+                }
+                if variants.len() != 0 {
+                    self.expect_token(Token::Comma)?;
+                    self.advance();
+                    current = self.expect_current(vec![Either::A(Token::RightCurlyBracket)])?;
+                    if current.data == Token::RightCurlyBracket {
+                        break;
+                    }
+                }
+                if let Token::Identifier(variant_id) = current.data.clone() {
+                    let variant_identifier = current.convert(variant_id);
+                    self.advance();
+                    let mut value = None;
+                    if let Some(Positioned { data: Token::Equal, .. }) = self.current() {
+                        self.advance();
+                        value = Some(self.parse_expr()?);
+                    }
+                    variants.push((variant_identifier, value));
+                } else {
+                    return Err(current.clone().convert(ParserError::UnexpectedToken(current.data, vec![Either::B("Identifier".to_string())])));
+                }
+            }
+            self.advance();
+
+            let end = self.nth(-1).unwrap().end.clone();
+            Ok(Positioned::new(Node::EnumDefinition(identifier, backing_type, variants), start, end))
+        } else {
+            Err(current.clone().convert(ParserError::UnexpectedToken(current.data, vec![Either::B("Identifier".to_string())])))
+        }
+    }
+
+    fn parse_union_definition(&mut self, start: Position) -> Result<Positioned<Node>, Positioned<ParserError>> {
+        self.advance();
+
+        let mut current = self.expect_current(vec![Either::B("Identifier".to_string())])?;
+        return if let Token::Identifier(id) = current.data.clone() {
+            let identifier = current.convert(id);
+            self.advance();
+
+            // Variants
+            self.expect_token(Token::LeftCurlyBracket)?;
+            self.advance();
+            let mut variants = vec![];
+            loop {
+                current = self.expect_current(vec![Either::A(Token::RightCurlyBracket)])?;
+                if current.data == Token::RightCurlyBracket && variants.len() != 0 {
+                    break;
+                }
+                if variants.len() != 0 {
+                    self.expect_token(Token::Comma)?;
+                    self.advance();
+                    current = self.expect_current(vec![Either::A(Token::RightCurlyBracket)])?;
+                    if current.data == Token::RightCurlyBracket {
+                        break;
+                    }
+                }
+                if let Token::Identifier(variant_id) = current.data.clone() {
+                    let variant_identifier = current.convert(variant_id);
+                    self.advance();
+                    let mut payload = vec![];
+                    if let Some(Positioned { data: Token::LeftParenthesis, .. }) = self.current() {
+                        self.advance();
+                        loop {
+                            current = self.expect_current(vec![Either::A(Token::RightParenthesis)])?;
+                            if current.data == Token::RightParenthesis && payload.len() != 0 {
+                                break;
+                            }
+                            if payload.len() != 0 {
+                                self.expect_token(Token::Comma)?;
+                                self.advance();
+                            }
+                            payload.push(self.parse_type()?);
+                            self.advance();
+                        }
+                        self.advance();
+                    }
+                    variants.push((variant_identifier, payload));
+                } else {
+                    return Err(current.clone().convert(ParserError::UnexpectedToken(current.data, vec![Either::B("Identifier".to_string())])));
+                }
+            }
+            self.advance();
+
+            let end = self.nth(-1).unwrap().end.clone();
+            Ok(Positioned::new(Node::UnionDefinition(identifier, variants), start, end))
+        } else {
+            Err(current.clone().convert(ParserError::UnexpectedToken(current.data, vec![Either::B("Identifier".to_string())])))
+        }
+    }
+
+    fn parse_impl(&mut self, start: Position) -> Result<Positioned<Node>, Positioned<ParserError>> {
+        self.advance();
+
+        let mut current = self.expect_current(vec![Either::B("Identifier".to_string())])?;
+        return if let Token::Identifier(id) = current.data.clone() {
+            let identifier = current.convert(id);
+            self.advance();
+
+            // Methods
+            self.expect_token(Token::LeftCurlyBracket)?;
+            self.advance();
+            let mut methods = vec![];
+            loop {
+                current = self.expect_current(vec![Either::A(Token::RightCurlyBracket)])?;
+                match current.data {
+                    Token::RightCurlyBracket => break,
+                    Token::Keyword(Keyword::Fn) => methods.push(self.parse_function_definition(current.start.clone())?),
+                    _ => return Err(current.clone().convert(ParserError::UnexpectedToken(current.data, vec![Either::A(Token::Keyword(Keyword::Fn)), Either::A(Token::RightCurlyBracket)]))),
+                }
+            }
+            self.advance();
+
+            let end = self.nth(-1).unwrap().end.clone();
+            Ok(Positioned::new(Node::Impl(identifier, methods), start, end))
+        } else {
+            Err(current.clone().convert(ParserError::UnexpectedToken(current.data, vec![Either::B("Identifier".to_string())])))
+        }
+    }
+
+    fn parse_match(&mut self, start: Position) -> Result<Positioned<Node>, Positioned<ParserError>> {
+        self.advance();
+        let value = self.parse_expr()?;
+
+        // Arms
+        self.expect_token(Token::LeftCurlyBracket)?;
+        self.advance();
+        let mut arms = vec![];
+        loop {
+            let mut current = self.expect_current(vec![Either::A(Token::RightCurlyBracket)])?;
+            if current.data == Token::RightCurlyBracket {
+                break;
+            }
+            if let Token::Identifier(variant_id) = current.data.clone() {
+                let variant = current.convert(variant_id);
+                self.advance();
+
+                // Bindings
+                let mut bindings = vec![];
+                if let Some(Positioned { data: Token::LeftParenthesis, .. }) = self.current() {
+                    self.advance();
+                    loop {
+                        current = self.expect_current(vec![Either::A(Token::RightParenthesis)])?;
+                        if current.data == Token::RightParenthesis && bindings.len() != 0 {
+                            break;
+                        }
+                        if bindings.len() != 0 {
+                            self.expect_token(Token::Comma)?;
+                            self.advance();
+                            current = self.expect_current(vec![Either::B("Identifier".to_string())])?;
+                        }
+                        if let Token::Identifier(binding) = current.data.clone() {
+                            bindings.push((current.convert(binding), None));
+                            self.advance();
+                        } else {
+                            return Err(current.clone().convert(ParserError::UnexpectedToken(current.data, vec![Either::B("Identifier".to_string())])));
+                        }
+                    }
+                    self.advance();
+                }
+
+                // Body
+                self.expect_token(Token::FatArrow)?;
+                self.advance();
+                current = self.expect_current(vec![Either::A(Token::LeftCurlyBracket)])?;
+                let body = if current.data == Token::LeftCurlyBracket {
+                    let body = self.parse_block()?;
+                    if let Some(Positioned { data: Token::Comma, .. }) = self.current() {
+                        self.advance();
+                    }
+                    body
+                } else {
+                    vec![self.parse_current()?]
+                };
+                arms.push((variant, bindings, body));
+            } else {
+                return Err(current.clone().convert(ParserError::UnexpectedToken(current.data, vec![Either::B("Identifier".to_string())])));
+            }
+        }
+        self.advance();
+
+        let end = self.nth(-1).unwrap().end.clone();
+        return Ok(Positioned::new(Node::Match(Box::new(value), None, arms), start, end));
+    }
+
+    fn parse_switch(&mut self, start: Position) -> Result<Positioned<Node>, Positioned<ParserError>> {
+        self.advance();
+        let value = self.parse_expr()?;
+
+        // Cases
+        self.expect_token(Token::LeftCurlyBracket)?;
+        self.advance();
+        let mut cases = vec![];
+        let mut else_body = None;
+        loop {
+            let mut current = self.expect_current(vec![Either::A(Token::RightCurlyBracket)])?;
+            if current.data == Token::RightCurlyBracket {
+                break;
+            }
+            if else_body.is_some() {
+                // The else case comes last
+                return Err(current.clone().convert(ParserError::UnexpectedToken(current.data, vec![Either::A(Token::RightCurlyBracket)])));
+            }
+
+            // Patterns
+            let mut patterns = vec![];
+            if current.data == Token::Keyword(Keyword::Else) {
+                self.advance();
+            } else {
+                loop {
+                    let pattern = self.parse_expr()?;
+                    let range_end = match self.current() {
+                        Some(Positioned { data: Token::DoubleDot, .. }) => {
+                            self.advance();
+                            Some((self.parse_expr()?, false))
+                        }
+                        Some(Positioned { data: Token::DoubleDotEqual, .. }) => {
+                            self.advance();
+                            Some((self.parse_expr()?, true))
+                        }
+                        _ => None,
+                    };
+                    patterns.push((pattern, range_end));
+                    if let Some(Positioned { data: Token::Comma, .. }) = self.current() {
+                        self.advance();
+                        continue;
+                    }
+                    break;
+                }
+            }
+
+            // Body
+            self.expect_token(Token::FatArrow)?;
+            self.advance();
+            current = self.expect_current(vec![Either::A(Token::LeftCurlyBracket)])?;
+            let body = if current.data == Token::LeftCurlyBracket {
+                let body = self.parse_block()?;
+                if let Some(Positioned { data: Token::Comma, .. }) = self.current() {
+                    self.advance();
+                }
+                body
+            } else {
+                vec![self.parse_current()?]
+            };
+            if patterns.is_empty() {
+                else_body = Some(body);
+            } else {
+                cases.push((patterns, body));
+            }
+        }
+        self.advance();
+
+        let end = self.nth(-1).unwrap().end.clone();
+        return Ok(Positioned::new(Node::Switch(Box::new(value), None, cases, else_body), start, end));
+    }
+
     fn parse_return(&mut self, start: Position) -> Result<Positioned<Node>, Positioned<ParserError>> {
         self.advance();
         let expr = self.parse_expr()?;
@@ -561,6 +1232,13 @@ impl Parser {
         return Ok(Positioned::new(Node::Return(Box::new(expr)), start, end));
     }
 
+    fn parse_defer(&mut self, start: Position) -> Result<Positioned<Node>, Positioned<ParserError>> {
+        self.advance();
+        let statement = self.parse_current()?;
+        let end = statement.end.clone();
+        return Ok(Positioned::new(Node::Defer(Box::new(statement)), start, end));
+    }
+
     fn parse_keyword(&mut self, keyword: Positioned<Keyword>) -> Result<Positioned<Node>, Positioned<ParserError>> {
         return match keyword.data.clone() {
             Keyword::True | Keyword::False => {
@@ -588,34 +1266,75 @@ impl Parser {
                 Ok(expr)
             }
             Keyword::Fn => self.parse_function_definition(keyword.start.clone()),
+            Keyword::Comptime => {
+                let node = self.parse_comptime(keyword.start.clone())?;
+                self.advance();
+                Ok(node)
+            }
             Keyword::Return => {
                 let node = self.parse_return(keyword.start.clone())?;
                 self.expect_token(Token::Semicolon)?;
                 self.advance();
                 Ok(node)
             },
+            Keyword::If => self.parse_if(keyword.start.clone()),
+            Keyword::While => self.parse_while(keyword.start.clone()),
+            Keyword::For => self.parse_for(keyword.start.clone()),
+            Keyword::Struct => self.parse_struct_definition(keyword.start.clone()),
+            Keyword::Enum => self.parse_enum_definition(keyword.start.clone()),
+            Keyword::Union => self.parse_union_definition(keyword.start.clone()),
+            Keyword::Type => self.parse_type_alias(keyword.start.clone()),
+            Keyword::Match => self.parse_match(keyword.start.clone()),
+            Keyword::Switch => self.parse_switch(keyword.start.clone()),
+            Keyword::Impl => self.parse_impl(keyword.start.clone()),
+            Keyword::Defer => self.parse_defer(keyword.start.clone()),
+            Keyword::Break => {
+                self.advance();
+                self.expect_token(Token::Semicolon)?;
+                self.advance();
+                Ok(keyword.convert(Node::Break))
+            }
+            Keyword::Continue => {
+                self.advance();
+                self.expect_token(Token::Semicolon)?;
+                self.advance();
+                Ok(keyword.convert(Node::Continue))
+            }
             kw => Err(keyword.convert(ParserError::UnexpectedToken(Token::Keyword(kw), vec![]))),
         }
     }
 
-    fn handle_identifier(&mut self, id: Positioned<String>) -> Result<Positioned<Node>, Positioned<ParserError>> {
-        if let Some(next) = self.nth(1) {
-            match next.data {
-                Token::Equal => {
-                    self.advance();
-                    self.advance();
-                    let value = self.parse_expr()?;
-                    let start = id.start.clone();
-                    let end = value.end.clone();
-                    let node = Positioned::new(Node::VariableAssignment(false, id.clone(), Box::new(value)), start, end);
-                    self.expect_token(Token::Semicolon)?;
-                    self.advance();
-                    return Ok(node);
-                }
-                _ => {}
-            }
+    fn assignment_operator(token: &Positioned<Token>) -> Option<Option<Positioned<Operator>>> {
+        return match token.data {
+            Token::Equal => Some(None),
+            Token::PlusEqual => Some(Some(token.convert(Operator::Plus))),
+            Token::MinusEqual => Some(Some(token.convert(Operator::Minus))),
+            Token::StarEqual => Some(Some(token.convert(Operator::Multiply))),
+            Token::SlashEqual => Some(Some(token.convert(Operator::Divide))),
+            Token::PercentEqual => Some(Some(token.convert(Operator::Remainder))),
+            Token::AndEqual => Some(Some(token.convert(Operator::BitAnd))),
+            Token::PipeEqual => Some(Some(token.convert(Operator::BitOr))),
+            Token::HatEqual => Some(Some(token.convert(Operator::BitXor))),
+            Token::DoubleLeftAngleEqual => Some(Some(token.convert(Operator::LeftShift))),
+            Token::DoubleRightAngleEqual => Some(Some(token.convert(Operator::RightShift))),
+            _ => None,
         }
+    }
+
+    fn parse_expression_statement(&mut self) -> Result<Positioned<Node>, Positioned<ParserError>> {
         let expr = self.parse_expr()?;
+        if let Some(current) = self.current() {
+            if let Some(operator) = Self::assignment_operator(&current) {
+                self.advance();
+                let value = self.parse_expr()?;
+                let start = expr.start.clone();
+                let end = value.end.clone();
+                let node = Positioned::new(Node::Assignment(Box::new(expr), operator, Box::new(value)), start, end);
+                self.expect_token(Token::Semicolon)?;
+                self.advance();
+                return Ok(node);
+            }
+        }
         self.expect_token(Token::Semicolon)?;
         self.advance();
         return Ok(expr);
@@ -747,7 +1466,10 @@ impl Parser {
                     self.advance();
                     return self.parse_current();
                 }
-                Token::Identifier(id) => self.handle_identifier(current.convert(id)),
+                // Constant definition, as opposed to a `const ref` expression
+                Token::Keyword(Keyword::Const) if !matches!(self.nth(1).map(|next| next.data), Some(Token::Keyword(Keyword::Ref))) => {
+                    return self.parse_keyword(current.convert(Keyword::Const))
+                }
                 // Unary Operators
                 Token::Wave |
                 Token::Keyword(Keyword::Not) |
@@ -759,15 +1481,13 @@ impl Parser {
                 Token::Keyword(Keyword::Deref) |
                 // Structure
                 Token::LeftParenthesis |
+                Token::LeftSquareBracket |
                 // Value
+                Token::Identifier(_) |
                 Token::Number(_) |
                 Token::Char(_) |
-                Token::String(_) => {
-                    let expr = self.parse_expr()?;
-                    self.expect_token(Token::Semicolon)?;
-                    self.advance();
-                    return Ok(expr);
-                },
+                Token::String(_) => self.parse_expression_statement(),
+                Token::LeftCurlyBracket => self.parse_bare_block(current.start),
                 Token::At => return self.parse_compiler_instruction(current.start),
                 Token::Keyword(keyword) => return self.parse_keyword(current.convert(keyword)),
                 token => Err(current.convert(ParserError::UnexpectedToken(token, vec![]))),
@@ -1,36 +1,52 @@
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Token {
     Plus,
+    PlusEqual,
     Minus,
+    MinusEqual,
     Star,
+    StarEqual,
     Slash,
+    SlashEqual,
     Percent,
+    PercentEqual,
     Semicolon,
     Colon,
     Comma,
+    Dot,
     And,
+    AndEqual,
     DoubleAnd,
     Pipe,
+    PipeEqual,
     DoublePipe,
     Hat,
+    HatEqual,
     DoubleHat,
     Wave,
     At,
+    DoubleDot,
+    DoubleDotEqual,
     TripleDot,
     LeftAngle,
     DoubleLeftAngle,
+    DoubleLeftAngleEqual,
     LeftAngleEqual,
     RightAngle,
     DoubleRightAngle,
+    DoubleRightAngleEqual,
     RightAngleEqual,
     Equal,
     DoubleEqual,
+    FatArrow,
     ExclamationMark,
     ExclamationMarkEqual,
     LeftParenthesis,
     RightParenthesis,
     LeftCurlyBracket,
     RightCurlyBracket,
+    LeftSquareBracket,
+    RightSquareBracket,
     Number(String),
     Char(String),
     String(String),
@@ -58,6 +74,14 @@ pub enum Keyword {
     I16,
     I32,
     I64,
+    Usize,
+    Isize,
+    CInt,
+    CLong,
+    CSizeT,
+    CChar,
+    F32,
+    F64,
     Bool,
     Str,
     Char,
@@ -70,6 +94,21 @@ pub enum Keyword {
     Extern,
     Import,
     Include,
+    If,
+    Else,
+    While,
+    Break,
+    Continue,
+    For,
+    In,
+    Struct,
+    Enum,
+    Union,
+    Type,
+    Match,
+    Switch,
+    Impl,
+    Defer,
 }
 
 impl Keyword {
@@ -96,6 +135,14 @@ impl Keyword {
             "i16" => Some(Keyword::I16),
             "i32" => Some(Keyword::I32),
             "i64" => Some(Keyword::I64),
+            "usize" => Some(Keyword::Usize),
+            "isize" => Some(Keyword::Isize),
+            "c_int" => Some(Keyword::CInt),
+            "c_long" => Some(Keyword::CLong),
+            "c_size_t" => Some(Keyword::CSizeT),
+            "c_char" => Some(Keyword::CChar),
+            "f32" => Some(Keyword::F32),
+            "f64" => Some(Keyword::F64),
             "bool" => Some(Keyword::Bool),
             "str" => Some(Keyword::Str),
             "char" => Some(Keyword::Char),
@@ -108,6 +155,21 @@ impl Keyword {
             "extern" => Some(Keyword::Extern),
             "import" => Some(Keyword::Import),
             "include" => Some(Keyword::Include),
+            "if" => Some(Keyword::If),
+            "else" => Some(Keyword::Else),
+            "while" => Some(Keyword::While),
+            "break" => Some(Keyword::Break),
+            "continue" => Some(Keyword::Continue),
+            "for" => Some(Keyword::For),
+            "in" => Some(Keyword::In),
+            "struct" => Some(Keyword::Struct),
+            "enum" => Some(Keyword::Enum),
+            "union" => Some(Keyword::Union),
+            "type" => Some(Keyword::Type),
+            "match" => Some(Keyword::Match),
+            "switch" => Some(Keyword::Switch),
+            "impl" => Some(Keyword::Impl),
+            "defer" => Some(Keyword::Defer),
             _ => None
         }
     }
@@ -3,6 +3,9 @@ use crate::{Node, Positioned};
 use crate::cnode::{CNode, COperator, CType, CValueNode};
 use crate::node::{CompilerInstruction, DataType, Operator, ValueNode, VarType};
 
+// Largest range of a switch case expanded into case labels
+const MAX_CASE_RANGE: i128 = 32;
+
 pub enum TranspilerError {
 
 }
@@ -15,10 +18,24 @@ impl Display for TranspilerError {
 
 }
 
+enum Breakable {
+    Loop(Option<String>),
+    Switch,
+}
+
 pub struct Transpiler {
     src: String,
     ast: Vec<Positioned<Node>>,
-    index: usize
+    index: usize,
+    breakables: Vec<Breakable>,
+    // Deferred statements of each enclosing body, flagged when the body is a loop's
+    defers: Vec<(bool, Vec<Positioned<Node>>)>,
+    return_type: Option<Positioned<DataType>>,
+    temp_count: usize,
+    headers: Vec<String>,
+    prelude_types: Vec<String>,
+    prelude_structs: Vec<Positioned<CNode>>,
+    prelude_functions: Vec<Positioned<CNode>>,
 }
 
 impl Transpiler {
@@ -27,7 +44,15 @@ impl Transpiler {
         return Self {
             src,
             ast,
-            index: 0
+            index: 0,
+            breakables: vec![],
+            defers: vec![],
+            return_type: None,
+            temp_count: 0,
+            headers: vec![],
+            prelude_types: vec![],
+            prelude_structs: vec![],
+            prelude_functions: vec![],
         }
     }
 
@@ -43,6 +68,108 @@ impl Transpiler {
         return self.ast.get(self.index).cloned();
     }
 
+    fn make_temp(&mut self, prefix: &str) -> String {
+        self.temp_count += 1;
+        return format!("__{}_{}", prefix, self.temp_count);
+    }
+
+    fn require_header(&mut self, header: &str) {
+        if !self.headers.iter().any(|other| other == header) {
+            self.headers.push(header.to_string());
+        }
+    }
+
+    fn define_slice(&mut self, position: Positioned<()>, name: String, data_type: Positioned<CType>) {
+        self.require_header("assert");
+        self.require_header("stddef");
+        self.require_header("stdint");
+
+        let node = |data: CNode| Box::new(position.convert(data));
+        let var = |name: &str| Box::new(position.convert(CNode::VariableCall(name.to_string())));
+        let id = |name: &str| position.convert(name.to_string());
+        let op = |operator: COperator| position.convert(operator);
+        let ptr_type = position.convert(CType::Ref(Box::new(data_type)));
+        let size_type = position.convert(CType::Size);
+        let slice_type = position.convert(CType::Struct(name.clone()));
+
+        // struct { T* ptr; size_t len; }
+        self.prelude_structs.push(position.convert(CNode::StructDefinition(id(&name), vec![(ptr_type.clone(), id("ptr")), (size_type.clone(), id("len"))])));
+
+        // Slice__T__from(ptr, len, start, end), the view [start, end) of a buffer of len elements
+        let check = node(CNode::BinaryOperation(
+            node(CNode::BinaryOperation(var("start"), op(COperator::LessOrEqual), var("end"))),
+            op(COperator::And),
+            node(CNode::BinaryOperation(var("end"), op(COperator::LessOrEqual), var("len")))
+        ));
+        let result = node(CNode::StructLiteral(id(&name), vec![
+            (id("ptr"), *node(CNode::BinaryOperation(var("ptr"), op(COperator::Plus), var("start")))),
+            (id("len"), *node(CNode::BinaryOperation(var("end"), op(COperator::Minus), var("start")))),
+        ]));
+        self.prelude_functions.push(position.convert(CNode::FunctionDefinition(
+            slice_type.clone(),
+            id(&format!("{}__from", name)),
+            vec![(ptr_type.clone(), id("ptr")), (size_type.clone(), id("len")), (size_type.clone(), id("start")), (size_type.clone(), id("end"))],
+            vec![*node(CNode::FunctionCall(id("assert"), vec![*check])), *node(CNode::Return(result))]
+        )));
+
+        // Slice__T__sub(slice, start, end)
+        let result = node(CNode::FunctionCall(id(&format!("{}__from", name)), vec![
+            *node(CNode::FieldAccess(var("slice"), id("ptr"))),
+            *node(CNode::FieldAccess(var("slice"), id("len"))),
+            *var("start"),
+            *var("end"),
+        ]));
+        self.prelude_functions.push(position.convert(CNode::FunctionDefinition(
+            slice_type.clone(),
+            id(&format!("{}__sub", name)),
+            vec![(slice_type.clone(), id("slice")), (size_type.clone(), id("start")), (size_type.clone(), id("end"))],
+            vec![*node(CNode::Return(result))]
+        )));
+
+        // Slice__T__at(slice, index), a pointer to the element
+        let check = node(CNode::BinaryOperation(var("index"), op(COperator::Less), node(CNode::FieldAccess(var("slice"), id("len")))));
+        let result = node(CNode::BinaryOperation(node(CNode::FieldAccess(var("slice"), id("ptr"))), op(COperator::Plus), var("index")));
+        self.prelude_functions.push(position.convert(CNode::FunctionDefinition(
+            ptr_type,
+            id(&format!("{}__at", name)),
+            vec![(slice_type, id("slice")), (size_type, id("index"))],
+            vec![*node(CNode::FunctionCall(id("assert"), vec![*check])), *node(CNode::Return(result))]
+        )));
+    }
+
+    fn define_str(&mut self, position: Positioned<()>) {
+        self.require_header("stddef");
+
+        // struct { const char* ptr; size_t len; }
+        let ptr_type = position.convert(CType::ConstRef(Box::new(position.convert(CType::Char))));
+        let size_type = position.convert(CType::Size);
+        self.prelude_structs.push(position.convert(CNode::StructDefinition(position.convert("str".to_string()), vec![
+            (ptr_type, position.convert("ptr".to_string())),
+            (size_type, position.convert("len".to_string())),
+        ])));
+    }
+
+    fn slice_name(&mut self, inner: Positioned<DataType>) -> Result<String, Positioned<TranspilerError>> {
+        return if let CType::Struct(name) = self.transpile_type(inner.convert(DataType::Slice(Box::new(inner.clone()))))?.data {
+            Ok(name)
+        } else {
+            panic!("Should not happen!");
+        }
+    }
+
+    fn string_length(value: &str) -> usize {
+        // Escape sequences are stored as written and count as one byte
+        let mut length = 0;
+        let mut bytes = value.bytes();
+        while let Some(byte) = bytes.next() {
+            if byte == b'\\' {
+                bytes.next();
+            }
+            length += 1;
+        }
+        return length;
+    }
+
     fn transpile_operator(&mut self, operator: Positioned<Operator>) -> Result<Positioned<COperator>, Positioned<TranspilerError>> {
         return match operator.data {
             Operator::Plus => Ok(operator.convert(COperator::Plus)),
@@ -106,10 +233,23 @@ impl Transpiler {
 
     fn transpile_value(&mut self, value: Positioned<ValueNode>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
         match value.data.clone() {
-            ValueNode::Number(num) => Ok(value.convert(CNode::Value(CValueNode::Number(num)))),
+            ValueNode::Number(num) => Ok(value.convert(CNode::Value(CValueNode::Number(Self::integer_literal(num))))),
+            ValueNode::Float(num) => Ok(value.convert(CNode::Value(CValueNode::Number(num)))),
             ValueNode::String(str) => Ok(value.convert(CNode::Value(CValueNode::String(str)))),
             ValueNode::Char(chr) => Ok(value.convert(CNode::Value(CValueNode::Char(chr)))),
-            ValueNode::Boolean(bool) => Ok(value.convert(CNode::Value(CValueNode::Number(if bool { "1".to_string() } else { "0".to_string() })))),
+            ValueNode::Boolean(bool) => {
+                self.require_header("stdbool");
+                Ok(value.convert(CNode::Value(CValueNode::Number(bool.to_string()))))
+            }
+        }
+    }
+
+    fn integer_literal(num: String) -> String {
+        // C literals are never negative, and need a suffix above INT64_MAX
+        return match num.parse::<i128>() {
+            Ok(number) if number == i64::MIN as i128 => format!("({} - 1)", i64::MIN as i128 + 1),
+            Ok(number) if number > i64::MAX as i128 => format!("{}ULL", num),
+            _ => num,
         }
     }
 
@@ -122,25 +262,80 @@ impl Transpiler {
     }
 
     fn transpile_type(&mut self, data_type: Positioned<DataType>) -> Result<Positioned<CType>, Positioned<TranspilerError>> {
+        // Fixed-width integers and bool come from the C standard headers
+        match data_type.data {
+            DataType::U8 | DataType::U16 | DataType::U32 | DataType::U64 |
+            DataType::I8 | DataType::I16 | DataType::I32 | DataType::I64 => self.require_header("stdint"),
+            DataType::Bool | DataType::ComptimeBool => self.require_header("stdbool"),
+            _ => {}
+        }
+
         match data_type.data.clone() {
             DataType::ComptimeNumber => return Ok(data_type.convert(CType::Int)),
+            DataType::ComptimeFloat => return Ok(data_type.convert(CType::Double)),
             DataType::ComptimeString => return Ok(data_type.clone().convert(CType::ConstRef(Box::new(data_type.convert(CType::Char))))),
             DataType::ComptimeChar => return Ok(data_type.convert(CType::Char)),
-            DataType::ComptimeBool => return Ok(data_type.convert(CType::Int)),
-            DataType::U8 => return Ok(data_type.convert(CType::UnsignedByte)),
-            DataType::U16 => return Ok(data_type.convert(CType::UnsignedShort)),
-            DataType::U32 => return Ok(data_type.convert(CType::UnsignedInt)),
-            DataType::U64 => return Ok(data_type.convert(CType::UnsignedLong)),
-            DataType::I8 => return Ok(data_type.convert(CType::Byte)),
-            DataType::I16 => return Ok(data_type.convert(CType::Short)),
-            DataType::I32 => return Ok(data_type.convert(CType::Int)),
-            DataType::I64 => return Ok(data_type.convert(CType::Long)),
-            DataType::String => todo!("need Custom type and std lib"),
-            DataType::Bool => return Ok(data_type.convert(CType::Int)),
+            DataType::ComptimeBool => return Ok(data_type.convert(CType::Bool)),
+            DataType::U8 => return Ok(data_type.convert(CType::UnsignedInt8)),
+            DataType::U16 => return Ok(data_type.convert(CType::UnsignedInt16)),
+            DataType::U32 => return Ok(data_type.convert(CType::UnsignedInt32)),
+            DataType::U64 => return Ok(data_type.convert(CType::UnsignedInt64)),
+            DataType::I8 => return Ok(data_type.convert(CType::Int8)),
+            DataType::I16 => return Ok(data_type.convert(CType::Int16)),
+            DataType::I32 => return Ok(data_type.convert(CType::Int32)),
+            DataType::I64 => return Ok(data_type.convert(CType::Int64)),
+            DataType::Usize => {
+                self.require_header("stddef");
+                return Ok(data_type.convert(CType::Size));
+            }
+            DataType::Isize => {
+                self.require_header("stddef");
+                return Ok(data_type.convert(CType::PtrDiff));
+            }
+            DataType::CInt => return Ok(data_type.convert(CType::Int)),
+            DataType::CLong => return Ok(data_type.convert(CType::Long)),
+            DataType::F32 => return Ok(data_type.convert(CType::Float)),
+            DataType::F64 => return Ok(data_type.convert(CType::Double)),
+            DataType::String => {
+                if !self.prelude_types.contains(&"str".to_string()) {
+                    self.prelude_types.push("str".to_string());
+                    self.define_str(data_type.convert(()));
+                }
+                return Ok(data_type.convert(CType::Struct("str".to_string())));
+            }
+            DataType::Bool => return Ok(data_type.convert(CType::Bool)),
             DataType::Char => return Ok(data_type.convert(CType::Char)),
             DataType::Ref(inner) => return Ok(data_type.convert(CType::Ref(Box::new(self.transpile_type(*inner)?)))),
             DataType::ConstRef(inner) => return Ok(data_type.convert(CType::ConstRef(Box::new(self.transpile_type(*inner)?)))),
             DataType::Void => return Ok(data_type.convert(CType::Void)),
+            DataType::Custom(_) => panic!("Should not happen!"),
+            DataType::Struct(name) => return Ok(data_type.convert(CType::Struct(name))),
+            DataType::Enum(name) => return Ok(data_type.convert(CType::Enum(name))),
+            DataType::Union(name) => return Ok(data_type.convert(CType::Struct(name))),
+            DataType::Alias(name, inner) => {
+                // The aliased type still needs its headers and prelude
+                self.transpile_type(*inner)?;
+                return Ok(data_type.convert(CType::Typedef(name)));
+            }
+            DataType::Array(inner, size) => return Ok(data_type.convert(CType::Array(Box::new(self.transpile_type(*inner)?), size))),
+            DataType::Slice(inner) => {
+                let name = format!("Slice__{}", inner.data.mangle());
+                if !self.prelude_types.contains(&name) {
+                    self.prelude_types.push(name.clone());
+                    let c_inner = self.transpile_type(*inner)?;
+                    self.define_slice(data_type.convert(()), name.clone(), c_inner);
+                }
+                return Ok(data_type.convert(CType::Struct(name)));
+            }
+            DataType::Function(params, return_type) => {
+                // Function values are pointers to functions
+                let mut c_params = vec![];
+                for param in params {
+                    c_params.push(self.transpile_type(param)?);
+                }
+                let c_function = data_type.convert(CType::Function(Box::new(self.transpile_type(*return_type)?), c_params));
+                return Ok(data_type.convert(CType::Ref(Box::new(c_function))));
+            }
         }
     }
 
@@ -169,6 +364,20 @@ impl Transpiler {
         let c_left = self.transpile_node(left.clone())?;
         let c_right = self.transpile_type(right.clone())?;
 
+        if let DataType::String = right.data {
+            // The length of string literals is known at compile time, other strings are measured at runtime
+            let c_len = if let CNode::Value(CValueNode::String(ref value)) = c_left.data {
+                left.convert(CNode::Value(CValueNode::Number(Self::string_length(value).to_string())))
+            } else {
+                self.require_header("string");
+                left.convert(CNode::FunctionCall(left.convert("strlen".to_string()), vec![c_left.clone()]))
+            };
+            return Ok(Positioned::new(CNode::StructLiteral(left.convert("str".to_string()), vec![
+                (left.convert("ptr".to_string()), c_left.clone()),
+                (left.convert("len".to_string()), c_len),
+            ]), left.start, right.end));
+        }
+
         return Ok(Positioned::new(CNode::Casting(Box::new(c_left), c_right), left.start, right.end));
     }
 
@@ -176,15 +385,8 @@ impl Transpiler {
         return Ok(id.clone().convert(CNode::VariableCall(id.data.clone())));
     }
 
-    fn transpile_variable_assignment(&mut self, deref: bool, id: Positioned<String>, value: Positioned<Node>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
-        let c_value = self.transpile_node(value.clone())?;
-        let start = id.start.clone();
-        let end = value.end.clone();
-        return Ok(Positioned::new(CNode::VariableAssignment(deref, id.clone(), Box::new(c_value)), start, end));
-    }
-
     fn transpile_function_definition(&mut self, position: Positioned<()>, name: Positioned<String>, params: Vec<(Positioned<String>, Positioned<DataType>)>, return_type: Option<Positioned<DataType>>, body: Vec<Positioned<Node>>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
-        let c_type = self.transpile_type(return_type.unwrap_or(name.convert(DataType::Void)))?;
+        let c_type = self.transpile_type(return_type.clone().unwrap_or(name.convert(DataType::Void)))?;
 
         let mut c_params = Vec::new();
         for (param_name, param_type) in params {
@@ -192,16 +394,52 @@ impl Transpiler {
             c_params.push((c_param_type, param_name.clone()));
         }
 
-        let mut c_body = Vec::new();
-        for node in body {
-            c_body.push(self.transpile_node(node)?);
-        }
+        self.return_type = return_type;
+        let c_body = self.transpile_body(body)?;
 
         Ok(position.convert(CNode::FunctionDefinition(c_type, name.clone(), c_params, c_body)))
     }
 
     fn transpile_return(&mut self, position: Positioned<()>, node: Positioned<Node>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
-        Ok(position.convert(CNode::Return(Box::new(self.transpile_node(node)?))))
+        let c_value = self.transpile_node(node)?;
+        let mut c_deferred = self.transpile_deferred(self.defers.len())?;
+        if c_deferred.is_empty() {
+            return Ok(position.convert(CNode::Return(Box::new(c_value))));
+        }
+
+        // The value is computed before the deferred statements run
+        let temp = self.make_temp("result");
+        let c_type = self.transpile_type(self.return_type.clone().unwrap())?;
+        let mut c_block = vec![position.convert(CNode::VariableDef(c_type, false, position.convert(temp.clone()), Some(Box::new(c_value))))];
+        c_block.append(&mut c_deferred);
+        c_block.push(position.convert(CNode::Return(Box::new(position.convert(CNode::VariableCall(temp))))));
+        Ok(position.convert(CNode::Block(c_block)))
+    }
+
+    fn transpile_deferred(&mut self, depth: usize) -> Result<Vec<Positioned<CNode>>, Positioned<TranspilerError>> {
+        // Innermost scope first, each in reverse order of declaration
+        let mut deferred = vec![];
+        for (_, statements) in self.defers[self.defers.len() - depth..].iter().rev() {
+            deferred.extend(statements.iter().rev().cloned());
+        }
+        let mut c_deferred = vec![];
+        for statement in deferred {
+            c_deferred.push(self.transpile_node(statement)?);
+        }
+        return Ok(c_deferred);
+    }
+
+    fn loop_defer_depth(&self) -> usize {
+        return self.defers.len() - self.defers.iter().rposition(|(is_loop, _)| *is_loop).unwrap();
+    }
+
+    fn with_deferred(position: Positioned<()>, mut c_deferred: Vec<Positioned<CNode>>, c_exit: Positioned<CNode>) -> Positioned<CNode> {
+        return if c_deferred.is_empty() {
+            c_exit
+        } else {
+            c_deferred.push(c_exit);
+            position.convert(CNode::Block(c_deferred))
+        }
     }
 
     fn transpile_function_call(&mut self, position: Positioned<()>, name: Positioned<String>, params: Vec<Positioned<Node>>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
@@ -213,6 +451,423 @@ impl Transpiler {
         Ok(position.convert(CNode::FunctionCall(name, r_params)))
     }
 
+    fn transpile_body(&mut self, body: Vec<Positioned<Node>>) -> Result<Vec<Positioned<CNode>>, Positioned<TranspilerError>> {
+        return self.transpile_scope(body, false);
+    }
+
+    fn transpile_scope(&mut self, body: Vec<Positioned<Node>>, is_loop: bool) -> Result<Vec<Positioned<CNode>>, Positioned<TranspilerError>> {
+        self.defers.push((is_loop, vec![]));
+
+        let mut c_body = Vec::new();
+        let mut exits = false;
+        for node in body {
+            match node.data {
+                Node::Defer(statement) => {
+                    self.defers.last_mut().unwrap().1.push(*statement);
+                    continue;
+                }
+                Node::Return(_) | Node::Break | Node::Continue => exits = true,
+                _ => exits = false,
+            }
+            c_body.push(self.transpile_node(node)?);
+        }
+
+        // Falling off the end of the scope runs its deferred statements
+        if !exits {
+            c_body.append(&mut self.transpile_deferred(1)?);
+        }
+        self.defers.pop();
+
+        return Ok(c_body);
+    }
+
+    fn transpile_if(&mut self, position: Positioned<()>, branches: Vec<(Positioned<Node>, Vec<Positioned<Node>>)>, else_body: Option<Vec<Positioned<Node>>>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
+        let mut c_branches = Vec::new();
+        for (condition, body) in branches {
+            let c_condition = self.transpile_node(condition)?;
+            let c_body = self.transpile_body(body)?;
+            c_branches.push((c_condition, c_body));
+        }
+
+        let c_else_body = if let Some(else_body) = else_body {
+            Some(self.transpile_body(else_body)?)
+        } else {
+            None
+        };
+
+        Ok(position.convert(CNode::If(c_branches, c_else_body)))
+    }
+
+    fn transpile_loop_body(&mut self, body: Vec<Positioned<Node>>) -> Result<(Vec<Positioned<CNode>>, Option<String>), Positioned<TranspilerError>> {
+        self.breakables.push(Breakable::Loop(None));
+        let c_body = self.transpile_scope(body, true)?;
+        return if let Some(Breakable::Loop(label)) = self.breakables.pop() {
+            Ok((c_body, label))
+        } else {
+            panic!("Should not happen!");
+        }
+    }
+
+    fn with_break_label(position: Positioned<()>, c_loop: Positioned<CNode>, label: Option<String>) -> Positioned<CNode> {
+        // A `break` inside a switch jumps to a label placed right after the loop
+        return if let Some(label) = label {
+            position.convert(CNode::Block(vec![c_loop, position.convert(CNode::Label(label))]))
+        } else {
+            c_loop
+        }
+    }
+
+    fn transpile_while(&mut self, position: Positioned<()>, condition: Positioned<Node>, body: Vec<Positioned<Node>>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
+        let c_condition = self.transpile_node(condition)?;
+        let (c_body, label) = self.transpile_loop_body(body)?;
+
+        Ok(Self::with_break_label(position.clone(), position.convert(CNode::While(Box::new(c_condition), c_body)), label))
+    }
+
+    fn transpile_for(&mut self, position: Positioned<()>, name: Positioned<String>, data_type: Option<Positioned<DataType>>, range_start: Positioned<Node>, range_end: Positioned<Node>, inclusive: bool, body: Vec<Positioned<Node>>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
+        let c_type = self.transpile_type(data_type.unwrap())?;
+        let c_start = self.transpile_node(range_start)?;
+        let c_end = self.transpile_node(range_end.clone())?;
+        let (c_body, label) = self.transpile_loop_body(body)?;
+
+        // The end is only evaluated once
+        let (c_end, c_end_def) = if let CNode::Value(_) = c_end.data {
+            (c_end, None)
+        } else {
+            let temp = range_end.convert(self.make_temp("end"));
+            let c_temp = range_end.convert(CNode::VariableDef(c_type.clone(), true, temp.clone(), Some(Box::new(c_end))));
+            (range_end.convert(CNode::VariableCall(temp.data)), Some(c_temp))
+        };
+        // An inclusive loop stops on its last value, the end may be the maximum of the type
+        let flag = if inclusive {
+            Some(position.convert(self.make_temp("next")))
+        } else {
+            None
+        };
+
+        let c_for = Self::with_break_label(position.clone(), position.convert(CNode::For(c_type, name, Box::new(c_start), Box::new(c_end), flag, c_body)), label);
+        return Ok(if let Some(c_end_def) = c_end_def {
+            position.convert(CNode::Block(vec![c_end_def, c_for]))
+        } else {
+            c_for
+        });
+    }
+
+    fn transpile_continue(&mut self, position: Positioned<()>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
+        let c_deferred = self.transpile_deferred(self.loop_defer_depth())?;
+        return Ok(Self::with_deferred(position.clone(), c_deferred, position.convert(CNode::Continue)));
+    }
+
+    fn transpile_break(&mut self, position: Positioned<()>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
+        let c_deferred = self.transpile_deferred(self.loop_defer_depth())?;
+        let c_break = self.transpile_break_jump(position.clone())?;
+        return Ok(Self::with_deferred(position, c_deferred, c_break));
+    }
+
+    fn transpile_break_jump(&mut self, position: Positioned<()>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
+        let mut in_switch = false;
+        for index in (0..self.breakables.len()).rev() {
+            match &self.breakables[index] {
+                Breakable::Switch => in_switch = true,
+                Breakable::Loop(label) => {
+                    if !in_switch {
+                        return Ok(position.convert(CNode::Break));
+                    }
+                    let label = if let Some(label) = label {
+                        label.clone()
+                    } else {
+                        let label = self.make_temp("break");
+                        self.breakables[index] = Breakable::Loop(Some(label.clone()));
+                        label
+                    };
+                    return Ok(position.convert(CNode::Goto(label)));
+                }
+            }
+        }
+        panic!("Should not happen!");
+    }
+
+    fn transpile_struct_definition(&mut self, position: Positioned<()>, name: Positioned<String>, fields: Vec<(Positioned<String>, Positioned<DataType>)>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
+        let mut c_fields = Vec::new();
+        for (field_name, field_type) in fields {
+            let c_field_type = self.transpile_type(field_type)?;
+            c_fields.push((c_field_type, field_name));
+        }
+
+        Ok(position.convert(CNode::StructDefinition(name, c_fields)))
+    }
+
+    fn transpile_enum_definition(&mut self, position: Positioned<()>, name: Positioned<String>, backing_type: Option<Positioned<DataType>>, variants: Vec<(Positioned<String>, Option<Positioned<Node>>)>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
+        let c_backing_type = self.transpile_type(backing_type.unwrap())?;
+
+        let mut c_variants = Vec::new();
+        for (variant_name, variant_value) in variants {
+            if let Some(Positioned { data: Node::Value(ValueNode::Number(num)), .. }) = variant_value {
+                c_variants.push((variant_name.convert(format!("{}__{}", name.data, variant_name.data)), Self::integer_literal(num)));
+            } else {
+                panic!("Should not happen!");
+            }
+        }
+
+        Ok(position.convert(CNode::EnumDefinition(name, c_backing_type, c_variants)))
+    }
+
+    fn transpile_struct_literal(&mut self, position: Positioned<()>, name: Positioned<String>, fields: Vec<(Positioned<String>, Positioned<Node>)>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
+        let mut c_fields = Vec::new();
+        for (field_name, field_value) in fields {
+            let c_field_value = self.transpile_node(field_value)?;
+            c_fields.push((field_name, c_field_value));
+        }
+
+        Ok(position.convert(CNode::StructLiteral(name, c_fields)))
+    }
+
+    fn transpile_union_definition(&mut self, position: Positioned<()>, name: Positioned<String>, variants: Vec<(Positioned<String>, Vec<Positioned<DataType>>)>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
+        let mut c_variants = Vec::new();
+        for (variant_name, payload) in variants {
+            let mut c_payload = Vec::new();
+            for p_type in payload {
+                c_payload.push(self.transpile_type(p_type)?);
+            }
+            c_variants.push((variant_name, c_payload));
+        }
+
+        Ok(position.convert(CNode::UnionDefinition(name, c_variants)))
+    }
+
+    fn transpile_type_alias(&mut self, position: Positioned<()>, name: Positioned<String>, data_type: Positioned<DataType>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
+        let c_type = self.transpile_type(data_type)?;
+        Ok(position.convert(CNode::Typedef(c_type, name)))
+    }
+
+    fn transpile_union_literal(&mut self, position: Positioned<()>, name: Positioned<String>, variant: Positioned<String>, args: Vec<Positioned<Node>>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
+        let mut c_args = Vec::new();
+        for arg in args {
+            c_args.push(self.transpile_node(arg)?);
+        }
+
+        Ok(position.convert(CNode::UnionLiteral(name, variant, c_args)))
+    }
+
+    fn transpile_match(&mut self, position: Positioned<()>, value: Positioned<Node>, union_name: Option<String>, arms: Vec<(Positioned<String>, Vec<(Positioned<String>, Option<Positioned<DataType>>)>, Vec<Positioned<Node>>)>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
+        let union_name = union_name.unwrap();
+        let c_value = self.transpile_node(value.clone())?;
+
+        // The value is only evaluated once
+        let (subject, temp) = if let CNode::VariableCall(_) = c_value.data {
+            (c_value, None)
+        } else {
+            let temp = value.convert(self.make_temp("match"));
+            let c_temp = value.convert(CNode::VariableDef(value.convert(CType::Struct(union_name.clone())), true, temp.clone(), Some(Box::new(c_value))));
+            (value.convert(CNode::VariableCall(temp.data)), Some(c_temp))
+        };
+
+        self.breakables.push(Breakable::Switch);
+        let mut c_cases = Vec::new();
+        for (variant, bindings, body) in arms {
+            let mut c_body = Vec::new();
+            for (index, (b_name, b_type)) in bindings.into_iter().enumerate() {
+                if b_name.data == "_" {
+                    continue;
+                }
+                let c_type = self.transpile_type(b_type.unwrap())?;
+                let c_payload = b_name.convert(CNode::FieldAccess(
+                    Box::new(b_name.convert(CNode::FieldAccess(Box::new(subject.clone()), variant.clone()))),
+                    b_name.convert(format!("_{}", index))
+                ));
+                c_body.push(b_name.convert(CNode::VariableDef(c_type, true, b_name.clone(), Some(Box::new(c_payload)))));
+            }
+            c_body.append(&mut self.transpile_body(body)?);
+            c_body.push(position.convert(CNode::Break));
+            let c_tag = variant.convert(CNode::VariableCall(format!("{}__{}", union_name, variant.data)));
+            c_cases.push((vec![c_tag], c_body));
+        }
+        self.breakables.pop();
+
+        let c_tag = subject.convert(CNode::FieldAccess(Box::new(subject.clone()), subject.convert("tag".to_string())));
+        let c_switch = position.convert(CNode::Switch(Box::new(c_tag), c_cases, None));
+        return Ok(if let Some(c_temp) = temp {
+            position.convert(CNode::Block(vec![c_temp, c_switch]))
+        } else {
+            c_switch
+        });
+    }
+
+    fn transpile_switch(&mut self, position: Positioned<()>, value: Positioned<Node>, value_type: Option<Positioned<DataType>>, cases: Vec<(Vec<(Positioned<Node>, Option<(Positioned<Node>, bool)>)>, Vec<Positioned<Node>>)>, else_body: Option<Vec<Positioned<Node>>>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
+        let case_number = |node: &Positioned<Node>| match &node.data {
+            Node::Value(ValueNode::Number(num)) => num.parse::<i128>().unwrap(),
+            _ => panic!("Should not happen!"),
+        };
+
+        // Ranges were normalized to inclusive ones by the Optimizer
+        let expand = cases.iter().flat_map(|(patterns, _)| patterns.iter()).all(|(pattern, range_end)| match range_end {
+            Some((range_end, _)) => case_number(range_end) - case_number(pattern) < MAX_CASE_RANGE,
+            None => true,
+        });
+        if expand {
+            let c_value = self.transpile_node(value)?;
+            self.breakables.push(Breakable::Switch);
+            let mut c_cases = Vec::new();
+            for (patterns, body) in cases {
+                let mut c_labels = Vec::new();
+                for (pattern, range_end) in patterns {
+                    if let Some((range_end, _)) = range_end {
+                        for number in case_number(&pattern)..=case_number(&range_end) {
+                            c_labels.push(pattern.convert(CNode::Value(CValueNode::Number(Self::integer_literal(number.to_string())))));
+                        }
+                    } else {
+                        c_labels.push(self.transpile_node(pattern)?);
+                    }
+                }
+                let mut c_body = self.transpile_body(body)?;
+                c_body.push(position.convert(CNode::Break));
+                c_cases.push((c_labels, c_body));
+            }
+            let c_default = if let Some(else_body) = else_body {
+                let mut c_body = self.transpile_body(else_body)?;
+                c_body.push(position.convert(CNode::Break));
+                Some(c_body)
+            } else {
+                None
+            };
+            self.breakables.pop();
+            return Ok(position.convert(CNode::Switch(Box::new(c_value), c_cases, c_default)));
+        }
+
+        // Large ranges fall back to an if chain, the value is only evaluated once
+        let c_value = self.transpile_node(value.clone())?;
+        let (subject, temp) = if let CNode::VariableCall(_) = c_value.data {
+            (c_value, None)
+        } else {
+            let c_type = self.transpile_type(value_type.unwrap())?;
+            let temp = value.convert(self.make_temp("switch"));
+            let c_temp = value.convert(CNode::VariableDef(c_type, true, temp.clone(), Some(Box::new(c_value))));
+            (value.convert(CNode::VariableCall(temp.data)), Some(c_temp))
+        };
+
+        let mut c_branches = Vec::new();
+        for (patterns, body) in cases {
+            let mut c_condition: Option<Positioned<CNode>> = None;
+            for (pattern, range_end) in patterns {
+                let c_pattern = self.transpile_node(pattern.clone())?;
+                let c_check = if let Some((range_end, _)) = range_end {
+                    let c_range_end = self.transpile_node(range_end)?;
+                    pattern.convert(CNode::BinaryOperation(
+                        Box::new(pattern.convert(CNode::BinaryOperation(Box::new(subject.clone()), pattern.convert(COperator::GreaterOrEqual), Box::new(c_pattern)))),
+                        pattern.convert(COperator::And),
+                        Box::new(pattern.convert(CNode::BinaryOperation(Box::new(subject.clone()), pattern.convert(COperator::LessOrEqual), Box::new(c_range_end))))
+                    ))
+                } else {
+                    pattern.convert(CNode::BinaryOperation(Box::new(subject.clone()), pattern.convert(COperator::Equal), Box::new(c_pattern)))
+                };
+                c_condition = Some(match c_condition {
+                    Some(c_condition) => pattern.convert(CNode::BinaryOperation(Box::new(c_condition), pattern.convert(COperator::Or), Box::new(c_check))),
+                    None => c_check,
+                });
+            }
+            let c_body = self.transpile_body(body)?;
+            c_branches.push((c_condition.unwrap(), c_body));
+        }
+        let c_else_body = if let Some(else_body) = else_body {
+            Some(self.transpile_body(else_body)?)
+        } else {
+            None
+        };
+
+        let c_if = position.convert(CNode::If(c_branches, c_else_body));
+        return Ok(if let Some(c_temp) = temp {
+            position.convert(CNode::Block(vec![c_temp, c_if]))
+        } else {
+            c_if
+        });
+    }
+
+    fn transpile_field_access(&mut self, position: Positioned<()>, value: Positioned<Node>, field: Positioned<String>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
+        let c_value = self.transpile_node(value)?;
+        Ok(position.convert(CNode::FieldAccess(Box::new(c_value), field)))
+    }
+
+    fn transpile_array_literal(&mut self, position: Positioned<()>, data_type: Option<Positioned<DataType>>, elements: Vec<Positioned<Node>>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
+        let size = elements.len();
+        let c_type = self.transpile_type(data_type.unwrap())?;
+
+        let mut c_elements = Vec::new();
+        for element in elements {
+            c_elements.push(self.transpile_node(element)?);
+        }
+
+        Ok(position.convert(CNode::ArrayLiteral(position.convert(CType::Array(Box::new(c_type), size)), c_elements)))
+    }
+
+    fn transpile_index(&mut self, position: Positioned<()>, value: Positioned<Node>, index: Positioned<Node>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
+        let c_value = self.transpile_node(value)?;
+        let c_index = self.transpile_node(index)?;
+        Ok(position.convert(CNode::Index(Box::new(c_value), Box::new(c_index))))
+    }
+
+    fn transpile_slice(&mut self, position: Positioned<()>, value: Positioned<Node>, base_type: Option<Positioned<DataType>>, range_start: Option<Box<Positioned<Node>>>, range_end: Option<Box<Positioned<Node>>>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
+        let base_type = base_type.unwrap();
+        let c_value = self.transpile_node(value)?;
+        let mut c_bounds = Vec::new();
+        if let (Some(range_start), Some(range_end)) = (range_start, range_end) {
+            c_bounds.push(self.transpile_node(*range_start)?);
+            c_bounds.push(self.transpile_node(*range_end)?);
+        }
+
+        return match base_type.data.clone() {
+            DataType::Array(inner, size) => {
+                let name = self.slice_name(*inner)?;
+                let c_size = position.convert(CNode::Value(CValueNode::Number(size.to_string())));
+                if c_bounds.is_empty() && matches!(c_value.data, CNode::ArrayLiteral(_, _)) {
+                    // Struct initializers would flatten the literal into a brace list
+                    c_bounds.push(position.convert(CNode::Value(CValueNode::Number("0".to_string()))));
+                    c_bounds.push(c_size.clone());
+                }
+                if c_bounds.is_empty() {
+                    // Whole array
+                    Ok(position.convert(CNode::StructLiteral(position.convert(name), vec![(position.convert("ptr".to_string()), c_value), (position.convert("len".to_string()), c_size)])))
+                } else {
+                    let mut c_params = vec![c_value, c_size];
+                    c_params.append(&mut c_bounds);
+                    Ok(position.convert(CNode::FunctionCall(position.convert(format!("{}__from", name)), c_params)))
+                }
+            }
+            DataType::Slice(inner) => {
+                let name = self.slice_name(*inner)?;
+                let mut c_params = vec![c_value];
+                c_params.append(&mut c_bounds);
+                Ok(position.convert(CNode::FunctionCall(position.convert(format!("{}__sub", name)), c_params)))
+            }
+            DataType::Ref(inner) => {
+                // The length behind a pointer is unknown
+                let name = self.slice_name(*inner)?;
+                let mut c_params = vec![c_value, position.convert(CNode::VariableCall("SIZE_MAX".to_string()))];
+                c_params.append(&mut c_bounds);
+                Ok(position.convert(CNode::FunctionCall(position.convert(format!("{}__from", name)), c_params)))
+            }
+            _ => panic!("Should not happen!"),
+        }
+    }
+
+    fn transpile_slice_index(&mut self, position: Positioned<()>, value: Positioned<Node>, index: Positioned<Node>, data_type: Option<Positioned<DataType>>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
+        let name = self.slice_name(data_type.unwrap())?;
+        let c_value = self.transpile_node(value)?;
+        let c_index = self.transpile_node(index)?;
+        let c_element = position.convert(CNode::FunctionCall(position.convert(format!("{}__at", name)), vec![c_value, c_index]));
+        Ok(position.convert(CNode::UnaryOperation(position.convert(COperator::Deref), Box::new(c_element))))
+    }
+
+    fn transpile_assignment(&mut self, position: Positioned<()>, target: Positioned<Node>, operator: Option<Positioned<Operator>>, value: Positioned<Node>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
+        let c_target = self.transpile_node(target)?;
+        let c_operator = if let Some(operator) = operator {
+            Some(self.transpile_operator(operator)?)
+        } else {
+            None
+        };
+        let c_value = self.transpile_node(value)?;
+        Ok(position.convert(CNode::Assignment(Box::new(c_target), c_operator, Box::new(c_value))))
+    }
+
     fn transpile_include(&mut self, file: Positioned<String>) -> Result<Positioned<CNode>, Positioned<TranspilerError>> {
         return Ok(file.convert(CNode::Include(file.clone())));
     }
@@ -234,23 +889,132 @@ impl Transpiler {
             Node::VariableDefinition(var_type, name, data_type, value) => self.transpile_variable_def(var_type, name, data_type, value),
             Node::Casting(left, right) => self.translate_casting(*left, right),
             Node::VariableCall(id) => self.transpile_variable_call(node.convert(id)),
-            Node::VariableAssignment(deref, id, value) => self.transpile_variable_assignment(deref, id, *value),
-            Node::FunctionDefinition(name, params, return_type, body) => self.transpile_function_definition(position, name, params, return_type, body),
+            Node::FunctionDefinition(name, _, params, return_type, body) => self.transpile_function_definition(position, name, params, return_type, body),
             Node::Return(node) => self.transpile_return(position, *node),
             Node::FunctionCall(name, params) => self.transpile_function_call(position, name, params),
+            Node::If(branches, else_body) => self.transpile_if(position, branches, else_body),
+            Node::While(condition, body) => self.transpile_while(position, *condition, body),
+            Node::For(name, data_type, range_start, range_end, inclusive, body) => self.transpile_for(position, name, data_type, *range_start, *range_end, inclusive, body),
+            Node::StructDefinition(name, fields) => self.transpile_struct_definition(position, name, fields),
+            Node::StructLiteral(name, fields) => self.transpile_struct_literal(position, name, fields),
+            Node::EnumDefinition(name, backing_type, variants) => self.transpile_enum_definition(position, name, backing_type, variants),
+            Node::EnumVariant(name, variant) => Ok(position.convert(CNode::VariableCall(format!("{}__{}", name.data, variant.data)))),
+            Node::UnionDefinition(name, variants) => self.transpile_union_definition(position, name, variants),
+            Node::TypeAlias(name, data_type) => self.transpile_type_alias(position, name, data_type),
+            Node::UnionLiteral(name, variant, args) => self.transpile_union_literal(position, name, variant, args),
+            Node::Match(value, union_name, arms) => self.transpile_match(position, *value, union_name, arms),
+            Node::Switch(value, value_type, cases, else_body) => self.transpile_switch(position, *value, value_type, cases, else_body),
+            Node::MemberCall(_, _, _) => panic!("Should not happen!"),
+            Node::Impl(_, _) => panic!("Should not happen!"),
+            Node::FieldAccess(value, field) => self.transpile_field_access(position, *value, field),
+            Node::ArrayLiteral(data_type, elements) => self.transpile_array_literal(position, data_type, elements),
+            Node::Index(value, index) => self.transpile_index(position, *value, *index),
+            Node::Slice(value, base_type, range_start, range_end) => self.transpile_slice(position, *value, base_type, range_start, range_end),
+            Node::SliceIndex(value, index, data_type) => self.transpile_slice_index(position, *value, *index, data_type),
+            Node::Assignment(target, operator, value) => self.transpile_assignment(position, *target, operator, *value),
+            Node::Block(body) => Ok(position.convert(CNode::Block(self.transpile_body(body)?))),
+            Node::Break => self.transpile_break(position),
+            Node::Defer(_) => panic!("Should not happen!"),
+            Node::Comptime(_) => panic!("Should not happen!"),
+            Node::Continue => self.transpile_continue(position),
             Node::CompilerInstruction(instruction) => self.transpile_compiler_instruction(position.convert(instruction)),
         }
     }
 
+    fn stored_struct(c_type: &CType) -> Option<String> {
+        return match c_type {
+            CType::Struct(name) => Some(name.clone()),
+            CType::Array(inner, _) => Self::stored_struct(&inner.data),
+            _ => None,
+        }
+    }
+
+    fn struct_dependencies(c_node: &Positioned<CNode>) -> Vec<String> {
+        let mut dependencies = Vec::new();
+        match &c_node.data {
+            CNode::StructDefinition(_, fields) => {
+                for (field_type, _) in fields.iter() {
+                    if let Some(name) = Self::stored_struct(&field_type.data) {
+                        dependencies.push(name);
+                    }
+                }
+            }
+            CNode::UnionDefinition(_, variants) => {
+                for (_, payload) in variants.iter() {
+                    for p_type in payload.iter() {
+                        if let Some(name) = Self::stored_struct(&p_type.data) {
+                            dependencies.push(name);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        return dependencies;
+    }
+
+    fn order_structs(&mut self, mut structs: Vec<Positioned<CNode>>) -> Vec<Positioned<CNode>> {
+        let mut names: Vec<String> = Vec::new();
+        let mut ordered = Vec::new();
+
+        // Forward declarations, so references can point to any struct
+        for c_struct in structs.iter() {
+            if let CNode::StructDefinition(name, _) | CNode::UnionDefinition(name, _) = &c_struct.data {
+                names.push(name.data.clone());
+                ordered.push(c_struct.convert(CNode::StructDeclaration(name.clone())));
+            }
+        }
+
+        // Definitions, a struct comes after the ones it contains by value
+        while !structs.is_empty() {
+            let index = structs.iter().position(|c_struct| {
+                Self::struct_dependencies(c_struct).iter().all(|dependency| !names.contains(dependency))
+            }).unwrap_or(0);
+            let c_struct = structs.remove(index);
+            if let CNode::StructDefinition(name, _) | CNode::UnionDefinition(name, _) = &c_struct.data {
+                names.retain(|other| *other != name.data);
+            }
+            ordered.push(c_struct);
+        }
+
+        return ordered;
+    }
+
     pub fn transpile(&mut self) -> Result<Vec<Positioned<CNode>>, Positioned<TranspilerError>> {
+        let mut includes = Vec::new();
+        let mut enums = Vec::new();
+        let mut structs = Vec::new();
+        let mut typedefs = Vec::new();
         let mut ast = Vec::new();
 
         while let Some(current) = self.current() {
-            ast.push(self.transpile_node(current)?);
+            let c_node = self.transpile_node(current)?;
+            match c_node.data {
+                CNode::Include(_) => includes.push(c_node),
+                CNode::EnumDefinition(_, _, _) => enums.push(c_node),
+                CNode::StructDefinition(_, _) |
+                CNode::UnionDefinition(_, _) => structs.push(c_node),
+                CNode::Typedef(_, _) => typedefs.push(c_node),
+                _ => ast.push(c_node),
+            }
             self.advance();
         }
 
-        return Ok(ast);
+        // Headers needed by the generated code
+        for header in self.headers.iter() {
+            includes.push(Positioned::no_pos(CNode::Include(Positioned::no_pos(header.clone()))));
+        }
+        structs.append(&mut self.prelude_structs);
+
+        // Types have to be defined before any use
+        let mut result = includes;
+        result.append(&mut enums);
+        result.append(&mut self.order_structs(structs));
+        result.append(&mut typedefs);
+        result.append(&mut self.prelude_functions);
+        result.append(&mut ast);
+
+        return Ok(result);
     }
 
 }
\ No newline at end of file